@@ -0,0 +1,73 @@
+#![allow(dead_code)]
+
+//! Test crate for derive-error-chain. If it runs, it's tested.
+
+#![cfg_attr(feature = "cargo-clippy", deny(clippy, clippy_pedantic))]
+#![cfg_attr(feature = "cargo-clippy", allow(
+	missing_docs_in_private_items,
+))]
+
+extern crate anyhow;
+#[macro_use]
+extern crate derive_error_chain;
+extern crate error_chain;
+
+fn main() {
+	anyhow_from_error();
+	anyhow_from_error_kind();
+}
+
+fn anyhow_from_error() {
+	mod inner {
+		#[derive(Debug, ErrorChain)]
+		pub enum ErrorKind {
+			Msg(String),
+
+			#[error_chain(foreign)]
+			Io(::std::io::Error),
+		}
+	}
+
+	#[derive(Debug, ErrorChain)]
+	#[error_chain(anyhow)]
+	pub enum ErrorKind {
+		Msg(String),
+
+		#[error_chain(link = "inner::Error")]
+		Inner(inner::ErrorKind),
+	}
+
+	fn load_config() -> Result<()> {
+		let result: ::std::result::Result<(), _> = Err(::std::io::Error::new(::std::io::ErrorKind::NotFound, "file not found"));
+		inner::ResultExt::chain_err(result, || "could not read config file")?;
+		Ok(())
+	}
+
+	fn run() -> anyhow::Result<()> {
+		load_config().chain_err(|| "could not load config").map_err(Error::into_anyhow)?;
+		Ok(())
+	}
+
+	let err = run().unwrap_err();
+	let chain: Vec<_> = err.chain().map(ToString::to_string).collect();
+	assert_eq!(chain, ["could not load config", "could not read config file", "file not found"]);
+}
+
+fn anyhow_from_error_kind() {
+	#[derive(Debug, ErrorChain)]
+	#[error_chain(anyhow)]
+	pub enum ErrorKind {
+		Msg(String),
+
+		#[error_chain(custom, display = r#"|code| write!(f, "HTTP status {}", code)"#)]
+		HttpStatus(u32),
+	}
+
+	fn run() -> anyhow::Result<()> {
+		Err(ErrorKind::HttpStatus(404))?
+	}
+
+	let err = run().unwrap_err();
+	assert_eq!(err.to_string(), "HTTP status 404");
+	assert_eq!(err.chain().count(), 1);
+}
@@ -0,0 +1,74 @@
+#![deny(deprecated)]
+
+//! Test crate for derive-error-chain. If it runs, it's tested.
+//!
+//! The generated code must not use deprecated items like `::std::error::Error::cause()` without allowing it,
+//! so that crates that deny `deprecated` can use the derive.
+
+#[macro_use]
+extern crate derive_error_chain;
+extern crate error_chain;
+
+mod inner {
+	#[derive(Debug, PartialEq, Eq, ErrorChain)]
+	#[error_chain(predicates, ord, from_boxed, into_boxed)]
+	pub enum ErrorKind {
+		Msg(String),
+
+		#[error_chain(custom)]
+		Code(i32),
+	}
+}
+
+#[derive(Debug, ErrorChain)]
+#[error_chain(location, dynamic_description, constructors, into_io)]
+pub enum ErrorKind {
+	Msg(String),
+
+	#[error_chain(link = "inner::Error")]
+	Inner(inner::ErrorKind),
+
+	#[error_chain(foreign)]
+	Io(::std::io::Error),
+
+	#[error_chain(foreign, context = "parsing: {0}")]
+	Parse(::std::num::ParseIntError),
+
+	#[error_chain(custom, cause = "|_, err| err")]
+	FileIO(::std::path::PathBuf, ::std::io::Error),
+
+	#[error_chain(custom, source_field = 1)]
+	Network(String, ::std::io::Error),
+}
+
+mod backtrace_env {
+	#[derive(Debug, ErrorChain)]
+	#[error_chain(backtrace_env = "DERIVE_ERROR_CHAIN_TESTS_BACKTRACE")]
+	pub enum ErrorKind {
+		Msg(String),
+
+		#[error_chain(foreign)]
+		Io(::std::io::Error),
+	}
+}
+
+fn main() {
+	use error_chain::ChainedError;
+
+	let err: Error = ::std::io::Error::new(::std::io::ErrorKind::NotFound, "file not found").into();
+	let err = Error::with_chain(err, "could not read config");
+	assert_eq!(err.iter().count(), 2);
+	assert_eq!(err.source_chain().count(), 2);
+	assert!(::std::error::Error::source(&err).is_some());
+	let _ = err.display_chain().to_string();
+
+	let err: Error = inner::Error::from_kind(inner::ErrorKind::Code(5)).into();
+	assert_eq!(err.to_string(), "Code");
+
+	let err: Error = "123abc".parse::<u32>().unwrap_err().into();
+	assert!(err.to_string().starts_with("parsing: "));
+
+	let err: backtrace_env::Error = ::std::io::Error::new(::std::io::ErrorKind::NotFound, "file not found").into();
+	let err: backtrace_env::Result<()> = backtrace_env::ResultExt::chain_err(Err(err), || "could not read config");
+	assert_eq!(err.unwrap_err().iter().count(), 2);
+}
@@ -0,0 +1,85 @@
+#![deny(missing_docs)]
+
+//! Test crate for derive-error-chain. If it compiles, it's tested.
+//!
+//! The generated items must all be documented, so that crates that deny `missing_docs` can use the derive.
+
+#[macro_use]
+extern crate derive_error_chain;
+extern crate error_chain;
+
+pub mod errors {
+	//! Errors with every optional item generated.
+
+	/// Inner errors.
+	pub mod inner {
+		/// The inner error kind.
+		#[derive(Debug, PartialEq, Eq, ErrorChain)]
+		#[error_chain(prelude = "prelude", predicates, ord, from_boxed, into_boxed, run, bail = "bail_inner")]
+		pub enum ErrorKind {
+			/// A message.
+			Msg(String),
+
+			/// A custom error.
+			#[error_chain(custom)]
+			Code(i32),
+		}
+	}
+
+	/// The error kind.
+	#[derive(Debug, ErrorChain)]
+	#[repr(u32)]
+	#[error_chain(location, error_partial_eq, must_use, dynamic_description, result_default)]
+	pub enum ErrorKind {
+		/// A message.
+		Msg(String),
+
+		/// A chainable link.
+		#[error_chain(link = "inner::Error")]
+		Inner(inner::ErrorKind),
+
+		/// A foreign link.
+		#[error_chain(foreign)]
+		Io(::std::io::Error),
+
+		/// A custom link.
+		#[error_chain(custom)]
+		NotFound = 404,
+	}
+
+	impl PartialEq for ErrorKind {
+		fn eq(&self, other: &Self) -> bool {
+			self.kind_name() == other.kind_name()
+		}
+	}
+
+	/// A generic error kind.
+	#[derive(Debug, ErrorChain)]
+	#[error_chain(error = "GenericError", result = "GenericResult", result_ext = "GenericResultExt")]
+	pub enum GenericErrorKind<T: ::std::fmt::Debug + Send + 'static> {
+		/// A message.
+		Msg(String),
+
+		/// A custom link.
+		#[error_chain(custom)]
+		Value(T),
+	}
+
+	/// Errors whose generated items only have the documentation given in the attributes.
+	pub mod custom_documentation {
+		/// The error kind of this module.
+		#[derive(Debug, ErrorChain)]
+		#[error_chain(error_doc = "The error type of this module.")]
+		#[error_chain(result_doc = "The result type of this module.")]
+		#[error_chain(result_ext_doc = "Extension methods for chaining errors of this module.")]
+		pub enum ErrorKind {
+			/// A message.
+			Msg(String),
+		}
+	}
+}
+
+fn main() {
+	let err: errors::Error = errors::ErrorKind::NotFound.into();
+	assert_eq!(err.kind().code(), Some(404));
+}
@@ -19,6 +19,11 @@ fn main() {
 	lambda_description_and_display_and_cause();
 	const_format_string_tuple_variants();
 	const_format_string_struct_variants();
+	empty_attributes_and_trailing_commas();
+	const_foreign_context();
+	const_format_string_escaped_braces();
+	path_description_and_display();
+	const_format_string_description();
 }
 
 fn macro_conflicts_use() {
@@ -118,6 +123,9 @@ fn lambda_description_and_display_and_cause() {
 		#[error_chain(custom)]
 		#[error_chain(cause = |_, err| err)]
 		FileIO(::std::path::PathBuf, ::std::io::Error),
+
+		#[error_chain(custom, cause = |_, err| err)]
+		FileIO2(::std::path::PathBuf, ::std::io::Error),
 	}
 
 	let err: Error = ErrorKind::HttpStatus(5).into();
@@ -126,6 +134,9 @@ fn lambda_description_and_display_and_cause() {
 
 	let err: Error = ErrorKind::FileIO(::std::path::PathBuf::new(), ::std::io::Error::from_raw_os_error(1)).into();
 	assert!(::std::error::Error::cause(&err).is_some());
+
+	let err: Error = ErrorKind::FileIO2(::std::path::PathBuf::new(), ::std::io::Error::from_raw_os_error(1)).into();
+	assert!(::std::error::Error::cause(&err).is_some());
 }
 
 fn const_format_string_tuple_variants() {
@@ -151,7 +162,7 @@ fn const_format_string_tuple_variants() {
 		Foreign(::std::io::Error),
 
 		#[error_chain(custom)]
-		#[error_chain(description = const("Custom's description: {0}"))]
+		#[error_chain(description = const("Custom's description"))]
 		#[error_chain(display = const("Custom's display: {0}"))]
 		Custom(u32, u32),
 	}
@@ -166,8 +177,7 @@ fn const_format_string_tuple_variants() {
 	assert_eq!("Foreign's display: abcde".to_string(), format!("{}", err));
 
 	let err: Error = ErrorKind::Custom(5, 6).into();
-	// No parameter substitution for `const()` description since it must be `&str`
-	assert_eq!("Custom's description: {0}", ::std::error::Error::description(&err));
+	assert_eq!("Custom's description", ::std::error::Error::description(&err));
 	assert_eq!("Custom's display: 5".to_string(), format!("{}", err));
 }
 
@@ -177,13 +187,144 @@ fn const_format_string_struct_variants() {
 		Msg(String),
 
 		#[error_chain(custom)]
-		#[error_chain(description = const("Custom's description: {code}"))]
+		#[error_chain(description = const("Custom's description"))]
 		#[error_chain(display = const("Custom's display: {code}"))]
 		Custom { code: u32, extra: u32, },
 	}
 
 	let err: Error = (ErrorKind::Custom { code: 5, extra: 6, }).into();
-	// No parameter substitution for `const()` description since it must be `&str`
-	assert_eq!("Custom's description: {code}", ::std::error::Error::description(&err));
+	assert_eq!("Custom's description", ::std::error::Error::description(&err));
 	assert_eq!("Custom's display: 5".to_string(), format!("{}", err));
 }
+
+fn empty_attributes_and_trailing_commas() {
+	#[derive(Debug, ErrorChain)]
+	pub enum ErrorKind {
+		Msg(String),
+
+		#[error_chain()]
+		#[error_chain(custom,)]
+		#[error_chain(display = |code| write!(f, "HTTP status {}", code),)]
+		HttpStatus(u32),
+
+		#[error_chain(custom, cause = |_, err| err,)]
+		FileIO(::std::path::PathBuf, ::std::io::Error),
+	}
+
+	assert_eq!(ErrorKind::HttpStatus(5).to_string(), "HTTP status 5");
+
+	let err: Error = ErrorKind::FileIO(::std::path::PathBuf::new(), ::std::io::Error::from_raw_os_error(1)).into();
+	assert!(::std::error::Error::cause(&err).is_some());
+}
+
+fn const_foreign_context() {
+	#[derive(Debug, ErrorChain)]
+	pub enum ErrorKind {
+		Msg(String),
+
+		#[error_chain(foreign, context = const("reading config: {0}"))]
+		Io(::std::io::Error),
+	}
+
+	let err: Error = ::std::io::Error::new(::std::io::ErrorKind::NotFound, "file not found").into();
+	assert_eq!(err.to_string(), "reading config: file not found");
+	assert!(::std::error::Error::cause(&err).is_none());
+}
+
+fn const_format_string_escaped_braces() {
+	#[derive(Debug, ErrorChain)]
+	pub enum ErrorKind {
+		Msg(String),
+
+		#[error_chain(custom)]
+		#[error_chain(description = const("100% {{done}}"))]
+		#[error_chain(display = const("100% {{done}} after {0} steps"))]
+		Tuple(u32),
+
+		#[error_chain(custom)]
+		#[error_chain(description = const("{{ left }} and }} right"))]
+		#[error_chain(display = const("{{steps}} = {steps}"))]
+		Struct { steps: u32 },
+	}
+
+	let err: Error = ErrorKind::Tuple(5).into();
+	assert_eq!(::std::error::Error::description(&err), "100% {done}");
+	assert_eq!(err.to_string(), "100% {done} after 5 steps");
+
+	let err: Error = (ErrorKind::Struct { steps: 5 }).into();
+	assert_eq!(::std::error::Error::description(&err), "{ left } and } right");
+	assert_eq!(err.to_string(), "{steps} = 5");
+}
+
+fn path_description_and_display() {
+	mod fmt {
+		pub fn code<T: ::std::fmt::Display>(f: &mut ::std::fmt::Formatter, code: &T) -> ::std::fmt::Result {
+			write!(f, "code {}", code)
+		}
+	}
+
+	trait Describe {
+		fn describe(code: &u32) -> &'static str;
+	}
+
+	struct Describer;
+
+	impl Describe for Describer {
+		fn describe(_: &u32) -> &'static str {
+			"described code"
+		}
+	}
+
+	#[derive(Debug, ErrorChain)]
+	pub enum ErrorKind {
+		Msg(String),
+
+		#[error_chain(custom)]
+		#[error_chain(description = <Describer as Describe>::describe)]
+		#[error_chain(display = fmt::code::<u32>)]
+		Code(u32),
+	}
+
+	let err: Error = ErrorKind::Code(5).into();
+	assert_eq!(::std::error::Error::description(&err), "described code");
+	assert_eq!(err.to_string(), "code 5");
+}
+
+fn const_format_string_description() {
+	#[derive(Debug, ErrorChain)]
+	pub enum ErrorKind {
+		Msg(String),
+
+		#[error_chain(custom)]
+		#[error_chain(description = const("invalid toolchain name"))]
+		#[error_chain(display = const("{description}: '{0}'"))]
+		Tuple(String),
+
+		#[error_chain(custom)]
+		#[error_chain(description = const("invalid toolchain name"))]
+		#[error_chain(display = const("{description}: '{name}'"))]
+		Struct { name: String },
+
+		#[error_chain(custom)]
+		#[error_chain(description = const("unknown toolchain"))]
+		#[error_chain(display = const("{description}!"))]
+		Unit,
+
+		// Without a `description` attribute, the default description is used
+		#[error_chain(custom)]
+		#[error_chain(display = const("{description} ({0})"))]
+		Undescribed(u32),
+	}
+
+	let err: Error = ErrorKind::Tuple("foo".to_string()).into();
+	assert_eq!(err.to_string(), "invalid toolchain name: 'foo'");
+
+	let err: Error = (ErrorKind::Struct { name: "foo".to_string() }).into();
+	assert_eq!(err.to_string(), "invalid toolchain name: 'foo'");
+
+	let err: Error = ErrorKind::Unit.into();
+	assert_eq!(err.to_string(), "unknown toolchain!");
+
+	let err: Error = ErrorKind::Undescribed(5).into();
+	assert_eq!(err.to_string(), "Undescribed (5)");
+}
@@ -13,6 +13,8 @@ extern crate derive_error_chain;
 
 fn main() {
 	can_disable_backtrace();
+	can_disable_backtrace_list_syntax();
+	const_constructor();
 }
 
 fn can_disable_backtrace() {
@@ -30,6 +32,37 @@ fn can_disable_backtrace() {
 	);
 }
 
+fn can_disable_backtrace_list_syntax() {
+	#[derive(Debug, ErrorChain)]
+	#[error_chain(backtrace(false))]
+	pub enum ErrorKind {
+		Msg(String),
+	}
+
+	let err: Error = ErrorKind::Msg("foo".to_string()).into();
+	assert!(err.backtrace().is_none());
+}
+
+fn const_constructor() {
+	#[derive(Debug, ErrorChain)]
+	#[error_chain(backtrace = "false")]
+	pub enum ErrorKind {
+		Msg(String),
+
+		#[error_chain(custom)]
+		Code(i32),
+	}
+
+	const NOT_FOUND: Error = Error::from_kind_const(ErrorKind::Code(404));
+
+	let err = NOT_FOUND;
+	assert!(err.backtrace().is_none());
+	assert_eq!(
+		r#"Error(Code(404), State { next_error: None })"#,
+		format!("{:?}", err)
+	);
+}
+
 #[deny(dead_code)]
 mod allow_dead_code {
 	#[derive(Debug, ErrorChain)]
@@ -0,0 +1,64 @@
+#![allow(dead_code)]
+
+//! Test crate for derive-error-chain. If it runs, it's tested.
+
+#![cfg_attr(feature = "cargo-clippy", deny(clippy, clippy_pedantic))]
+#![cfg_attr(feature = "cargo-clippy", allow(
+	missing_docs_in_private_items,
+))]
+
+#[macro_use]
+extern crate derive_error_chain;
+extern crate error_chain;
+#[macro_use]
+extern crate tracing;
+extern crate tracing_error;
+extern crate tracing_subscriber;
+
+fn main() {
+	use tracing_subscriber::layer::SubscriberExt;
+
+	let subscriber = tracing_subscriber::Registry::default().with(tracing_error::ErrorLayer::default());
+	tracing::subscriber::with_default(subscriber, || {
+		spantrace();
+		spantrace_with_location();
+	});
+}
+
+fn spantrace() {
+	#[derive(Debug, ErrorChain)]
+	#[error_chain(spantrace)]
+	pub enum ErrorKind {
+		Msg(String),
+
+		#[error_chain(foreign)]
+		Io(::std::io::Error),
+	}
+
+	#[instrument]
+	fn load_config() -> Result<()> {
+		Err(::std::io::Error::new(::std::io::ErrorKind::NotFound, "file not found").into())
+	}
+
+	let err = load_config().unwrap_err();
+	assert_eq!(err.spantrace().status(), tracing_error::SpanTraceStatus::CAPTURED);
+	assert!(err.spantrace().to_string().contains("load_config"));
+
+	let err: Error = "outside any span".into();
+	assert!(!err.spantrace().to_string().contains("load_config"));
+}
+
+fn spantrace_with_location() {
+	#[derive(Debug, ErrorChain)]
+	#[error_chain(location, spantrace)]
+	pub enum ErrorKind {
+		Msg(String),
+	}
+
+	let span = span!(tracing::Level::INFO, "parse_args");
+	let _guard = span.enter();
+
+	let err: Error = "bad args".into();
+	assert_eq!(err.location().file(), file!());
+	assert!(err.spantrace().to_string().contains("parse_args"));
+}
@@ -26,9 +26,12 @@ fn main() {
 	foreign_link_test::display_underlying_error();
 	foreign_link_test::finds_cause();
 	foreign_link_test::iterates();
+	foreign_link_test::struct_variant();
 
 	with_result();
 	without_result();
+	result_default();
+	without_result_ext();
 	documentation();
 	rustup_regression();
 	error_patterns();
@@ -40,6 +43,95 @@ fn main() {
 	test_without_msg_1();
 	test_without_msg_2();
 	macro_conflicts();
+	source_chain();
+	hygienic_generics();
+	hygienic_into_foreign();
+	hygienic_map_kind();
+	error_partial_eq();
+	prelude_test::public();
+	prelude_test::private();
+	context();
+	location();
+	empty_error_kind();
+	from_boxed();
+	kind_name();
+	raw_identifiers::test();
+	fallback_display();
+	must_use();
+	empty_attributes_and_trailing_commas();
+	ord();
+	chain_err_kind();
+	ok_or_kind();
+	foreign_context();
+	dynamic_description();
+	into_boxed();
+	generic_default();
+	visibility_test::test();
+	chain_iter_kinds();
+	no_from();
+	bail();
+	fallback_description();
+	boxed_foreign_link();
+	chain_err_name();
+	discriminant_code();
+	alternate_display();
+	no_error_description();
+	source_field();
+	result_error();
+	closures_with_context();
+	closure_calls_outer_call_fn();
+	standard_derives();
+	predicates();
+	run();
+	foreign_default_fields();
+	take_source();
+	box_str_msg();
+	foreign_source();
+	error_repr();
+	renamed_inner_link();
+	map_kind();
+	source_field_index();
+	into_io();
+	path_description_and_display();
+	error_debug();
+	causes();
+	chain_delimiter();
+	arc_foreign_link();
+	deprecated_test::test();
+	type_alias_link();
+	result_ok_default();
+	kind_mut();
+	force_from();
+	extract();
+	from_unit();
+	cfg_msg_test::test();
+	cfg_links_test::test();
+	context_description();
+	opaque_test::test();
+	debug_display();
+	same_link_twice();
+	user_state();
+	strict_msg();
+	move_and_annotated_closures();
+	anyhow_style_chain();
+	constructors();
+	generic_fn_scope(5u32);
+	generic_fn_scope("five");
+	format_args_msg();
+	backtrace_env();
+	from_str();
+	error_new();
+	into_foreign();
+	backtrace_from_chain();
+	thiserror_from_field();
+	error_eq_and_hash();
+	module_test::public();
+	module_test::private();
+
+	lifetimes_test::borrowed_foreign_link();
+	lifetimes_test::chain_err();
+	lifetimes_test::borrowed_custom_link();
+	lifetimes_test::internal_lifetime_name();
 }
 
 // Upstream tests
@@ -216,6 +308,32 @@ mod foreign_link_test {
 		}));
 		Ok(())
 	}
+
+	pub fn struct_variant() {
+		mod inner {
+			#[derive(Debug, ErrorChain)]
+			pub enum ErrorKind {
+				Msg(String),
+
+				#[error_chain(foreign)]
+				Foreign { source: super::ForeignError },
+			}
+		}
+
+		let chained_error: inner::Error = ForeignError { cause: ForeignErrorCause { } }.into();
+		match *chained_error.kind() {
+			inner::ErrorKind::Foreign { .. } => { },
+			_ => panic!(),
+		}
+		assert_eq!(
+			format!("{}", ForeignError { cause: ForeignErrorCause { } }),
+			format!("{}", chained_error)
+		);
+		assert_eq!(
+			format!("{}", ForeignErrorCause { }),
+			format!("{}", ::std::error::Error::cause(&chained_error).unwrap())
+		);
+	}
 }
 
 mod attributes_test {
@@ -248,76 +366,183 @@ mod attributes_test {
 	}
 }
 
-fn with_result() {
-	#[derive(Debug, ErrorChain)]
+fn error_eq_and_hash() {
+	#[derive(Debug, PartialEq, Eq, Hash, ErrorChain)]
+	#[error_chain(error_partial_eq, error_eq, error_hash)]
 	pub enum ErrorKind {
 		Msg(String),
+
+		#[error_chain(custom)]
+		Code(i32),
 	}
 
-	let _: Result<()> = Ok(());
+	let mut errors = ::std::collections::HashSet::new();
+	errors.insert(Error::from_kind(ErrorKind::Code(5)));
+	errors.insert(Error::with_chain(::std::io::Error::new(::std::io::ErrorKind::NotFound, "file not found"), ErrorKind::Code(5)));
+	assert_eq!(errors.len(), 1);
+
+	errors.insert(Error::from_kind(ErrorKind::Code(6)));
+	errors.insert("oops".into());
+	assert_eq!(errors.len(), 3);
+
+	mod generic {
+		#[derive(Debug, PartialEq, Eq, Hash, ErrorChain)]
+		#[error_chain(error_partial_eq, error_eq, error_hash)]
+		pub enum ErrorKind<__H: Send + ::std::fmt::Debug + ::std::hash::Hash + Eq + 'static> {
+			Msg(String),
+
+			#[error_chain(custom)]
+			Value(__H),
+		}
+	}
+
+	let mut errors = ::std::collections::HashSet::new();
+	errors.insert(generic::Error::from_kind(generic::ErrorKind::Value(5)));
+	errors.insert(generic::Error::from_kind(generic::ErrorKind::Value(5)));
+	assert_eq!(errors.len(), 1);
 }
 
-fn without_result() {
+fn thiserror_from_field() {
 	#[derive(Debug, ErrorChain)]
-	#[error_chain(result = "")]
 	pub enum ErrorKind {
 		Msg(String),
+
+		#[error_chain(custom)]
+		#[error_chain(display = r#"|_| write!(f, "I/O error")"#)]
+		Io(#[from] ::std::io::Error),
+
+		#[error_chain(custom)]
+		Fmt { #[from] err: ::std::fmt::Error, #[error_chain(default)] retries: u32 },
 	}
 
-	let _: Result<(), ()> = Ok(());
+	fn read() -> Result<()> {
+		Err(::std::io::Error::new(::std::io::ErrorKind::NotFound, "file not found"))?
+	}
+
+	let err = read().unwrap_err();
+	match *err.kind() {
+		ErrorKind::Io(ref err) => assert_eq!(err.kind(), ::std::io::ErrorKind::NotFound),
+		_ => unreachable!(),
+	}
+	assert_eq!(err.to_string(), "I/O error");
+	assert_eq!(::std::error::Error::source(&err).unwrap().to_string(), "file not found");
+
+	let err: Error = ::std::fmt::Error.into();
+	match *err.kind() {
+		ErrorKind::Fmt { retries: 0, .. } => (),
+		_ => unreachable!(),
+	}
+	assert!(::std::error::Error::source(&err).is_some());
 }
 
-fn documentation() {
+fn backtrace_from_chain() {
 	mod inner {
 		#[derive(Debug, ErrorChain)]
+		#[error_chain(backtrace_env = "DERIVE_ERROR_CHAIN_TESTS_INNER_BACKTRACE")]
 		pub enum ErrorKind {
 			Msg(String),
 		}
 	}
 
 	#[derive(Debug, ErrorChain)]
+	#[error_chain(backtrace_env = "DERIVE_ERROR_CHAIN_TESTS_OUTER_BACKTRACE")]
 	pub enum ErrorKind {
 		Msg(String),
 
-		/// Doc
 		#[error_chain(link = "inner::Error")]
 		Inner(inner::ErrorKind),
+	}
 
-		/// Doc
-		#[error_chain(foreign)]
-		Io(::std::io::Error),
+	// Hides the inner error from `extract_backtrace()`, so that the outer error doesn't take its backtrace when it's chained
+	#[derive(Debug)]
+	struct Wrapper(inner::Error);
 
-		/// Doc
-		#[error_chain(custom)]
-		Variant,
+	impl ::std::fmt::Display for Wrapper {
+		fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result { write!(f, "wrapper") }
+	}
+
+	impl ::std::error::Error for Wrapper {
+		fn source(&self) -> Option<&(::std::error::Error + 'static)> { Some(&self.0) }
 	}
+
+	::std::env::set_var("DERIVE_ERROR_CHAIN_TESTS_INNER_BACKTRACE", "1");
+	let inner_err: inner::Error = "inner".into();
+	::std::env::remove_var("DERIVE_ERROR_CHAIN_TESTS_INNER_BACKTRACE");
+	assert!(inner_err.backtrace().is_some());
+	let inner_backtrace = inner_err.backtrace().unwrap() as *const _;
+
+	let err = Error::with_chain(Wrapper(inner_err), "outer");
+	assert!(err.1.backtrace().is_none());
+	assert_eq!(err.backtrace().unwrap() as *const _, inner_backtrace);
+
+	let err: Error = "no backtrace".into();
+	assert!(err.backtrace().is_none());
 }
 
-mod multiple_error_same_mod {
+fn into_foreign() {
 	#[derive(Debug, ErrorChain)]
-	#[error_chain(error = "MyError", result_ext = "MyResultExt", result = "MyResult")]
-	pub enum MyErrorKind {
+	pub enum ErrorKind {
 		Msg(String),
+
+		#[error_chain(foreign)]
+		Io(::std::io::Error),
+
+		#[error_chain(foreign)]
+		Fmt { source: ::std::fmt::Error, #[error_chain(default)] retries: u32 },
 	}
 
+	let err: Error = ::std::io::Error::new(::std::io::ErrorKind::NotFound, "file not found").into();
+	let err: ::std::io::Error = err.into_foreign().unwrap();
+	assert_eq!(err.kind(), ::std::io::ErrorKind::NotFound);
+	assert_eq!(err.to_string(), "file not found");
+
+	let err: Error = ::std::io::Error::new(::std::io::ErrorKind::NotFound, "file not found").into();
+	let err = err.into_foreign::<::std::fmt::Error>().unwrap_err();
+	assert_eq!(err.to_string(), "file not found");
+
+	let err: Error = ::std::fmt::Error.into();
+	assert!(err.into_foreign::<::std::fmt::Error>().is_ok());
+
+	let err: Error = "msg".into();
+	let err = err.into_foreign::<::std::io::Error>().unwrap_err();
+	assert_eq!(err.to_string(), "msg");
+}
+
+fn error_new() {
 	#[derive(Debug, ErrorChain)]
 	pub enum ErrorKind {
 		Msg(String),
 	}
+
+	let err = Error::new(ErrorKind::Msg("x".into()));
+	match *err.kind() {
+		ErrorKind::Msg(ref s) if s == "x" => (),
+		_ => unreachable!(),
+	}
+	assert!(::std::error::Error::cause(&err).is_none());
 }
 
-#[deny(dead_code)]
-mod allow_dead_code {
+fn from_str() {
 	#[derive(Debug, ErrorChain)]
-	#[error_chain(result = "")]
+	#[error_chain(from_str)]
 	pub enum ErrorKind {
 		Msg(String),
+
+		#[error_chain(custom)]
+		Code(i32),
+	}
+
+	match "x".parse::<ErrorKind>() {
+		Ok(ErrorKind::Msg(ref s)) if s == "x" => (),
+		_ => unreachable!(),
 	}
+
+	let err: Error = "boom".parse::<ErrorKind>().unwrap().into();
+	assert_eq!(err.to_string(), "boom");
 }
 
-// Make sure links actually work!
-fn rustup_regression() {
-	mod mock {
+fn backtrace_env() {
+	mod inner {
 		#[derive(Debug, ErrorChain)]
 		pub enum ErrorKind {
 			Msg(String),
@@ -325,248 +550,2722 @@ fn rustup_regression() {
 	}
 
 	#[derive(Debug, ErrorChain)]
+	#[error_chain(backtrace_env = "DERIVE_ERROR_CHAIN_TESTS_BACKTRACE")]
 	pub enum ErrorKind {
 		Msg(String),
 
-		#[error_chain(link = "mock::Error")]
-		Download(mock::ErrorKind),
-
-		#[error_chain(custom)]
-		#[error_chain(description = r#"|| "could not locate working directory""#)]
-		LocatingWorkingDir,
+		#[error_chain(link = "inner::Error")]
+		Inner(inner::ErrorKind),
 	}
+
+	::std::env::set_var("DERIVE_ERROR_CHAIN_TESTS_BACKTRACE", "0");
+	let err: Error = "no backtrace".into();
+	assert!(err.backtrace().is_none());
+	let err = Error::with_chain(::std::io::Error::new(::std::io::ErrorKind::NotFound, "file not found"), "no backtrace");
+	assert!(err.backtrace().is_none());
+
+	::std::env::set_var("DERIVE_ERROR_CHAIN_TESTS_BACKTRACE", "1");
+	let err: Error = "backtrace".into();
+	assert!(err.backtrace().is_some());
+	let err: Result<()> = Err(::std::io::Error::new(::std::io::ErrorKind::NotFound, "file not found")).chain_err(|| "backtrace");
+	assert!(err.unwrap_err().backtrace().is_some());
+
+	::std::env::remove_var("DERIVE_ERROR_CHAIN_TESTS_BACKTRACE");
 }
 
-fn error_patterns() {
+fn format_args_msg() {
 	#[derive(Debug, ErrorChain)]
 	pub enum ErrorKind {
 		Msg(String),
 	}
 
-	// Tuples look nice when matching errors
-	match Error::from("Test") {
-		Error(ErrorKind::Msg(_), _) => {
+	let x = 5;
+
+	match ErrorKind::from(format_args!("x={}", x)) {
+		ErrorKind::Msg(ref s) if s == "x=5" => (),
+		_ => unreachable!(),
+	}
+
+	let err = Error::from(format_args!("x={}", x));
+	assert_eq!(err.to_string(), "x=5");
+
+	mod boxed {
+		#[derive(Debug, ErrorChain)]
+		pub enum ErrorKind {
+			Msg(Box<str>),
 		}
 	}
+
+	let err = boxed::Error::from(format_args!("x={}", x));
+	assert_eq!(err.to_string(), "x=5");
 }
 
-#[cfg_attr(feature = "cargo-clippy", allow(non_ascii_literal))]
-fn rewrapping() {
-	use std::env::VarError::{self, NotPresent, NotUnicode};
+// Items inside a function can't use the function's generics, so the `ErrorKind` has its own generic parameter
+// that the function instantiates with its generic parameter instead.
+fn generic_fn_scope<T: ::std::fmt::Debug + ::std::fmt::Display + Send + 'static>(value: T) {
+	mod inner {
+		#[derive(Debug, ErrorChain)]
+		pub enum ErrorKind {
+			Msg(String),
+		}
+	}
 
 	#[derive(Debug, ErrorChain)]
-	#[error_chain(error = "MyError", result_ext = "MyResultExt", result = "MyResult")]
-	pub enum MyErrorKind {
+	#[error_chain(predicates, constructors)]
+	pub enum ErrorKind<U: ::std::fmt::Debug + ::std::fmt::Display + Send + 'static> {
 		Msg(String),
 
-		#[error_chain(foreign)]
-		VarErr(VarError),
+		#[error_chain(link = "inner::Error")]
+		Inner(inner::ErrorKind),
+
+		#[error_chain(custom, display = r#"|value| write!(f, "invalid value {}", value)"#)]
+		InvalidValue(U),
 	}
 
-	let result_a_from_func: Result<String, _> = Err(VarError::NotPresent);
-	let result_b_from_func: Result<String, _> = Err(VarError::NotPresent);
+	fn check<U: ::std::fmt::Debug + ::std::fmt::Display + Send + 'static>(value: U) -> Result<U, ()> {
+		Err(ErrorKind::InvalidValue(value).into())
+	}
 
-	let our_error_a = result_a_from_func.map_err(|e| match e {
-		NotPresent => MyError::with_chain(e, "env var wasn't provided"),
-		NotUnicode(_) => MyError::with_chain(e, "env var was borkæ–‡å­—åŒ–ã"),
-	});
+	let expected = format!("invalid value {}", value);
+	let err = check(value).unwrap_err();
+	assert!(err.is_invalid_value());
+	assert_eq!(err.to_string(), expected);
 
-	let our_error_b = result_b_from_func.or_else(|e| match e {
-		NotPresent => Err(e).chain_err(|| "env var wasn't provided"),
-		NotUnicode(_) => Err(e).chain_err(|| "env var was borkæ–‡å­—åŒ–ã"),
-	});
+	let err: Error<T> = inner::Error::from("inner error").into();
+	assert!(err.is_inner());
 
-	assert_eq!(
-		format!("{}", our_error_a.unwrap_err()),
-		format!("{}", our_error_b.unwrap_err())
-	);
+	let err = Error::<T>::with_chain(::std::io::Error::new(::std::io::ErrorKind::NotFound, "file not found"), "outer error");
+	assert_eq!(err.iter().count(), 2);
 }
 
-// Own tests
-
-mod test2 {
+fn constructors() {
 	#[derive(Debug, ErrorChain)]
+	#[error_chain(constructors)]
 	pub enum ErrorKind {
 		Msg(String),
 
+		#[error_chain(foreign)]
+		Io(::std::io::Error),
+
 		#[error_chain(custom)]
 		HttpStatus(u32),
+
+		#[error_chain(custom)]
+		InvalidToken { line: u32, column: u32 },
+
+		#[error_chain(custom)]
+		Unknown,
 	}
-}
 
-fn public_api_test() {
-	use test2::{ Error, ErrorKind, ResultExt, Result };
+	match *Error::http_status(404).kind() {
+		ErrorKind::HttpStatus(404) => (),
+		_ => unreachable!(),
+	}
 
-	let err: Error = ErrorKind::HttpStatus(5).into();
-	let result: Result<()> = Err(err);
+	match *Error::invalid_token(1, 5).kind() {
+		ErrorKind::InvalidToken { line: 1, column: 5 } => (),
+		_ => unreachable!(),
+	}
 
-	let _: Result<()> = result.chain_err(|| "An HTTP error occurred");
+	match *Error::unknown().kind() {
+		ErrorKind::Unknown => (),
+		_ => unreachable!(),
+	}
+
+	assert_eq!(Error::msg("foo".to_string()).to_string(), "foo");
+	assert_eq!(Error::io(::std::io::Error::new(::std::io::ErrorKind::NotFound, "file not found")).to_string(), "file not found");
 }
 
-fn cause() {
+fn anyhow_style_chain() {
 	#[derive(Debug, ErrorChain)]
 	pub enum ErrorKind {
 		Msg(String),
-
-		#[error_chain(custom)]
-		#[error_chain(cause = "file_io_error_cause")]
-		FileIO(::std::path::PathBuf, ::std::io::Error),
 	}
 
-	fn file_io_error_cause<'a>(_: &::std::path::Path, err: &'a ::std::io::Error) -> &'a ::std::error::Error {
-		err
-	}
+	let err = Error::with_chain(::std::io::Error::new(::std::io::ErrorKind::NotFound, "file not found"), "could not read config");
+	let err = err.context("could not start");
 
-	let err: Error = ErrorKind::FileIO(::std::path::PathBuf::new(), ::std::io::Error::from_raw_os_error(1)).into();
-	assert!(::std::error::Error::cause(&err).is_some());
+	let chain: Vec<_> = err.chain().map(|err| err.to_string()).collect();
+	assert_eq!(chain, ["could not start", "could not read config", "file not found"]);
+	assert_eq!(err.chain().count(), 3);
+	assert_eq!(err.iter().count(), 3);
+
+	let err: Error = "no causes".into();
+	assert_eq!(err.chain().count(), 1);
 }
 
-fn inlined_description_and_display_and_cause() {
+fn move_and_annotated_closures() {
 	#[derive(Debug, ErrorChain)]
 	pub enum ErrorKind {
 		Msg(String),
 
 		#[error_chain(custom)]
-		#[error_chain(description = r#"|_| "http request returned an unsuccessful status code""#)]
-		#[error_chain(display = r#"|e| write!(f, "http request returned an unsuccessful status code: {}", e)"#)]
-		HttpStatus(u32),
+		#[error_chain(description = r#"move |_| "moved""#)]
+		#[error_chain(display = r#"move |t| write!(f, "moved {}", t)"#)]
+		Moved(u32),
 
 		#[error_chain(custom)]
-		#[error_chain(cause = "|_, err| err")]
-		FileIO(::std::path::PathBuf, ::std::io::Error),
+		#[error_chain(description = r#"|_: &u32| -> &'static str { "annotated" }"#)]
+		#[error_chain(display = r#"|t: &u32| -> ::std::fmt::Result { write!(f, "annotated {}", t) }"#)]
+		Annotated(u32),
+
+		#[error_chain(custom, cause = "move |_: &u32, err: &::std::io::Error| -> &::std::error::Error { err }")]
+		Caused(u32, ::std::io::Error),
 	}
 
-	let err: Error = ErrorKind::HttpStatus(5).into();
-	assert_eq!("http request returned an unsuccessful status code", ::std::error::Error::description(&err));
-	assert_eq!("http request returned an unsuccessful status code: 5".to_string(), format!("{}", err));
+	let err: Error = ErrorKind::Moved(5).into();
+	assert_eq!(::std::error::Error::description(&err), "moved");
+	assert_eq!(err.to_string(), "moved 5");
 
-	let err: Error = ErrorKind::FileIO(::std::path::PathBuf::new(), ::std::io::Error::from_raw_os_error(1)).into();
+	let err: Error = ErrorKind::Annotated(5).into();
+	assert_eq!(::std::error::Error::description(&err), "annotated");
+	assert_eq!(err.to_string(), "annotated 5");
+
+	let err: Error = ErrorKind::Caused(5, ::std::io::Error::from_raw_os_error(1)).into();
 	assert!(::std::error::Error::cause(&err).is_some());
 }
 
-mod generics_test {
-	use std::{error, fmt, io};
+fn strict_msg() {
+	#[derive(Debug, PartialEq, Eq, ErrorChain)]
+	#[error_chain(strict_msg, ord)]
+	pub enum ErrorKind {
+		Msg(String),
 
-	mod inner1 {
-		use std::fmt;
-		#[derive(Debug, ErrorChain)]
-		pub enum ErrorKind<T: Send + fmt::Debug + 'static> {
-			Msg(String),
+		#[error_chain(custom)]
+		Code(i32),
+	}
 
-			#[error_chain(custom)]
-			CustomGeneric(T)
-		}
+	assert!(ErrorKind::Msg("foo".to_string()) < ErrorKind::Code(5));
+}
+
+fn user_state() {
+	#[derive(Debug, Default)]
+	pub struct RequestState {
+		request_id: Option<u64>,
 	}
 
-	mod inner2 {
+	#[derive(Debug, ErrorChain)]
+	#[error_chain(location, state = "RequestState")]
+	pub enum ErrorKind {
+		Msg(String),
+
+		#[error_chain(foreign)]
+		Io(::std::io::Error),
+	}
+
+	let mut err: Error = "request failed".into();
+	assert_eq!(err.state().request_id, None);
+	err.state_mut().request_id = Some(5);
+	assert_eq!(err.state().request_id, Some(5));
+	assert_eq!(err.location().file(), file!());
+
+	let err: Error = ::std::io::Error::new(::std::io::ErrorKind::NotFound, "file not found").into();
+	assert_eq!(err.state().request_id, None);
+
+	let err: Result<()> = Err(err).chain_err(|| "chained");
+	assert_eq!(err.unwrap_err().state().request_id, None);
+}
+
+fn same_link_twice() {
+	mod inner {
 		#[derive(Debug, ErrorChain)]
 		pub enum ErrorKind {
 			Msg(String),
 		}
 	}
 
+	// The `From` impls of the two links would conflict
 	#[derive(Debug, ErrorChain)]
-	pub enum ErrorKind<T: error::Error + Send + 'static, U>
-		where U: Send + fmt::Debug + fmt::Display + 'static {
+	#[error_chain(no_from)]
+	pub enum ErrorKind {
 		Msg(String),
 
-		#[error_chain(custom)]
-		#[error_chain(description = r#"|_| "custom error""#)]
-		#[error_chain(display = r#"|t| write!(f, "custom error: {}", t)"#)]
-		Custom(String),
-
-		#[error_chain(custom)]
-		#[error_chain(description = r#"|_| "custom generic error""#)]
-		#[error_chain(display = r#"|t| write!(f, "custom generic error: {}", t)"#)]
-		CustomGeneric(T),
-
-		#[error_chain(custom)]
-		#[error_chain(description = r#"|_| "custom generic boxed error""#)]
-		#[error_chain(display = r#"|t| write!(f, "custom generic boxed error: {}", t)"#)]
-		CustomGenericBoxed(Box<U>),
-
-		#[error_chain(link = "inner1::Error<U>")]
-		LinkGeneric(inner1::ErrorKind<U>),
-
-		#[error_chain(link = "inner2::Error")]
-		Link(inner2::ErrorKind),
+		#[error_chain(link = "inner::Error")]
+		Reading(inner::ErrorKind),
 
-		#[error_chain(foreign)]
-		ForeignGeneric(T),
+		#[error_chain(link = "inner::Error")]
+		Writing(inner::ErrorKind),
+	}
 
-		#[error_chain(foreign)]
-		ForeignGenericBoxed(Box<T>),
+	let inner_err = inner::Error::from_kind(inner::ErrorKind::Msg("inner error".to_string()));
+	let has_backtrace = inner_err.backtrace().is_some();
+	let err = Error::with_chain(inner_err, ErrorKind::Msg("outer error".to_string()));
+	assert_eq!(err.backtrace().is_some(), has_backtrace);
 
-		#[error_chain(foreign)]
-		Foreign(io::Error),
+	let err = Error::from_kind(ErrorKind::Writing(inner::ErrorKind::Msg("inner error".to_string())));
+	match *err.kind() {
+		ErrorKind::Writing(inner::ErrorKind::Msg(ref s)) if s == "inner error" => (),
+		_ => unreachable!(),
 	}
 }
 
-fn test_without_msg_1() {
+fn debug_display() {
 	#[derive(Debug, ErrorChain)]
+	#[error_chain(debug_display)]
 	pub enum ErrorKind {
+		Msg(String),
+
 		#[error_chain(custom)]
-		HttpStatus(u32),
-	}
-}
+		Code(i32),
 
-fn test_without_msg_2() {
-	#[derive(Debug, ErrorChain)]
-	pub enum ErrorKind {
 		#[error_chain(custom)]
-		HttpStatus(u32),
-	}
+		Position { line: u32, column: u32 },
 
-	// Should not conflict
+		#[error_chain(custom)]
+		Unknown,
+
+		#[error_chain(custom, description = r#"|_| "described""#)]
+		Described(i32),
+	}
+
+	assert_eq!(ErrorKind::Code(5).to_string(), "Code(5)");
+	assert_eq!((ErrorKind::Position { line: 1, column: 2 }).to_string(), "Position { line: 1, column: 2 }");
+	assert_eq!(ErrorKind::Unknown.to_string(), "Unknown");
+	assert_eq!(ErrorKind::Described(5).to_string(), "described");
+	assert_eq!(ErrorKind::Msg("foo".to_string()).to_string(), "foo");
+}
+
+mod opaque_test {
+	mod errors {
+		#[derive(Debug, ErrorChain)]
+		#[error_chain(opaque)]
+		pub enum ErrorKind {
+			Msg(String),
+
+			#[error_chain(custom)]
+			Code(i32),
+		}
+
+		// The fields are still accessible inside the module
+		pub fn code(err: Error) -> Option<i32> {
+			match err {
+				Error(ErrorKind::Code(code), _) => Some(code),
+				_ => None,
+			}
+		}
+	}
+
+	pub fn test() {
+		let err = errors::Error::from_kind(errors::ErrorKind::Code(5));
+		match *err.kind() {
+			errors::ErrorKind::Code(5) => (),
+			_ => unreachable!(),
+		}
+		assert_eq!(errors::code(err), Some(5));
+
+		let err: errors::Error = "foo".into();
+		assert_eq!(err.to_string(), "foo");
+		assert_eq!(errors::code(err), None);
+	}
+}
+
+fn context_description() {
+	#[derive(Debug, ErrorChain)]
+	pub enum ErrorKind {
+		Msg(String),
+
+		#[error_chain(foreign, description = "|_| \"config error\"", context = "{description} - {0}")]
+		Io(::std::io::Error),
+	}
+
+	let err: Error = ::std::io::Error::new(::std::io::ErrorKind::NotFound, "file not found").into();
+	assert_eq!(err.to_string(), "config error - file not found");
+}
+
+mod cfg_links_test {
+	mod inner {
+		#[derive(Debug, ErrorChain)]
+		pub enum ErrorKind {
+			Msg(String),
+		}
+	}
+
+	#[derive(Debug, ErrorChain)]
+	#[error_chain(predicates)]
+	pub enum ErrorKind {
+		Msg(String),
+
+		#[cfg(foo)]
+		#[error_chain(link = "inner::Error")]
+		DisabledInner(inner::ErrorKind),
+
+		#[cfg(not(foo))]
+		#[error_chain(link = "inner::Error")]
+		EnabledInner(inner::ErrorKind),
+
+		#[cfg(foo)]
+		#[error_chain(foreign, extract)]
+		DisabledIo(::std::io::Error),
+
+		#[cfg(not(foo))]
+		#[error_chain(foreign, extract)]
+		EnabledIo(::std::io::Error),
+
+		#[cfg(foo)]
+		#[error_chain(custom, display = r#"|code| write!(f, "disabled {}", code)"#)]
+		DisabledCode(i32),
+
+		#[cfg(not(foo))]
+		#[error_chain(custom, display = r#"|code| write!(f, "enabled {}", code)"#)]
+		EnabledCode(i32),
+	}
+
+	pub fn test() {
+		let err: Error = inner::Error::from("inner error").into();
+		assert!(err.is_enabled_inner());
+		assert_eq!(err.kind().kind_name(), "EnabledInner");
+
+		let err: Error = ::std::io::Error::new(::std::io::ErrorKind::NotFound, "file not found").into();
+		assert!(err.is_enabled_io());
+		let err: ::std::io::Error = ::std::convert::TryFrom::try_from(err).unwrap();
+		assert_eq!(err.kind(), ::std::io::ErrorKind::NotFound);
+
+		let err: Error = ErrorKind::EnabledCode(5).into();
+		assert!(err.is_enabled_code());
+		assert_eq!(err.to_string(), "enabled 5");
+	}
+}
+
+mod cfg_msg_test {
+	mod enabled {
+		#[derive(Debug, ErrorChain)]
+		pub enum ErrorKind {
+			#[cfg(not(foo))]
+			Msg(String),
+		}
+
+		pub fn test() {
+			let err: Error = "foo".into();
+			match *err.kind() {
+				ErrorKind::Msg(ref s) if s == "foo" => (),
+				_ => unreachable!(),
+			}
+		}
+	}
+
+	mod disabled {
+		#[derive(Debug, ErrorChain)]
+		pub enum ErrorKind {
+			#[cfg(foo)]
+			Msg(String),
+
+			#[error_chain(custom)]
+			Code(i32),
+		}
+	}
+
+	pub fn test() {
+		enabled::test();
+		let _: disabled::Error = disabled::ErrorKind::Code(5).into();
+	}
+}
+
+fn with_result() {
+	#[derive(Debug, ErrorChain)]
+	pub enum ErrorKind {
+		Msg(String),
+	}
+
+	let _: Result<()> = Ok(());
+}
+
+fn without_result() {
+	#[derive(Debug, ErrorChain)]
+	#[error_chain(result = "")]
+	pub enum ErrorKind {
+		Msg(String),
+	}
+
+	let _: Result<(), ()> = Ok(());
+}
+
+fn result_default() {
+	#[derive(Debug, ErrorChain)]
+	#[error_chain(result = "Result", result_default)]
+	pub enum ErrorKind {
+		Msg(String),
+	}
+
+	let _: Result<()> = Err("foo".into());
+	let _: Result<(), ::std::fmt::Error> = Err(::std::fmt::Error);
+}
+
+fn without_result_ext() {
+	#[derive(Debug, ErrorChain)]
+	#[error_chain(result_ext = "")]
+	pub enum ErrorKind {
+		Msg(String),
+	}
+
+	trait ResultExt { }
+
+	let err: Error = ErrorKind::Msg("foo".to_string()).into();
+	let _: Result<()> = Err(err);
+}
+
+fn documentation() {
+	mod inner {
+		#[derive(Debug, ErrorChain)]
+		pub enum ErrorKind {
+			Msg(String),
+		}
+	}
+
+	#[derive(Debug, ErrorChain)]
+	pub enum ErrorKind {
+		Msg(String),
+
+		/// Doc
+		#[error_chain(link = "inner::Error")]
+		Inner(inner::ErrorKind),
+
+		/// Doc
+		#[error_chain(foreign)]
+		Io(::std::io::Error),
+
+		/// Doc
+		#[error_chain(custom)]
+		Variant,
+	}
+}
+
+mod multiple_error_same_mod {
+	#[derive(Debug, ErrorChain)]
+	#[error_chain(error = "MyError", result_ext = "MyResultExt", result = "MyResult")]
+	pub enum MyErrorKind {
+		Msg(String),
+	}
+
+	#[derive(Debug, ErrorChain)]
+	pub enum ErrorKind {
+		Msg(String),
+	}
+}
+
+#[deny(dead_code)]
+mod allow_dead_code {
+	#[derive(Debug, ErrorChain)]
+	#[error_chain(result = "")]
+	pub enum ErrorKind {
+		Msg(String),
+	}
+}
+
+// Make sure links actually work!
+fn rustup_regression() {
+	mod mock {
+		#[derive(Debug, ErrorChain)]
+		pub enum ErrorKind {
+			Msg(String),
+		}
+	}
+
+	#[derive(Debug, ErrorChain)]
+	pub enum ErrorKind {
+		Msg(String),
+
+		#[error_chain(link = "mock::Error")]
+		Download(mock::ErrorKind),
+
+		#[error_chain(custom)]
+		#[error_chain(description = r#"|| "could not locate working directory""#)]
+		LocatingWorkingDir,
+	}
+}
+
+fn error_patterns() {
+	#[derive(Debug, ErrorChain)]
+	pub enum ErrorKind {
+		Msg(String),
+	}
+
+	// Tuples look nice when matching errors
+	match Error::from("Test") {
+		Error(ErrorKind::Msg(_), _) => {
+		}
+	}
+}
+
+#[cfg_attr(feature = "cargo-clippy", allow(non_ascii_literal))]
+fn rewrapping() {
+	use std::env::VarError::{self, NotPresent, NotUnicode};
+
+	#[derive(Debug, ErrorChain)]
+	#[error_chain(error = "MyError", result_ext = "MyResultExt", result = "MyResult")]
+	pub enum MyErrorKind {
+		Msg(String),
+
+		#[error_chain(foreign)]
+		VarErr(VarError),
+	}
+
+	let result_a_from_func: Result<String, _> = Err(VarError::NotPresent);
+	let result_b_from_func: Result<String, _> = Err(VarError::NotPresent);
+
+	let our_error_a = result_a_from_func.map_err(|e| match e {
+		NotPresent => MyError::with_chain(e, "env var wasn't provided"),
+		NotUnicode(_) => MyError::with_chain(e, "env var was borkæ–‡å­—åŒ–ã"),
+	});
+
+	let our_error_b = result_b_from_func.or_else(|e| match e {
+		NotPresent => Err(e).chain_err(|| "env var wasn't provided"),
+		NotUnicode(_) => Err(e).chain_err(|| "env var was borkæ–‡å­—åŒ–ã"),
+	});
+
+	assert_eq!(
+		format!("{}", our_error_a.unwrap_err()),
+		format!("{}", our_error_b.unwrap_err())
+	);
+}
+
+// Own tests
+
+mod test2 {
+	#[derive(Debug, ErrorChain)]
+	pub enum ErrorKind {
+		Msg(String),
+
+		#[error_chain(custom)]
+		HttpStatus(u32),
+	}
+}
+
+fn public_api_test() {
+	use test2::{ Error, ErrorKind, ResultExt, Result };
+
+	let err: Error = ErrorKind::HttpStatus(5).into();
+	let result: Result<()> = Err(err);
+
+	let _: Result<()> = result.chain_err(|| "An HTTP error occurred");
+}
+
+fn cause() {
+	#[derive(Debug, ErrorChain)]
+	pub enum ErrorKind {
+		Msg(String),
+
+		#[error_chain(custom)]
+		#[error_chain(cause = "file_io_error_cause")]
+		FileIO(::std::path::PathBuf, ::std::io::Error),
+	}
+
+	fn file_io_error_cause<'a>(_: &::std::path::Path, err: &'a ::std::io::Error) -> &'a ::std::error::Error {
+		err
+	}
+
+	let err: Error = ErrorKind::FileIO(::std::path::PathBuf::new(), ::std::io::Error::from_raw_os_error(1)).into();
+	assert!(::std::error::Error::cause(&err).is_some());
+}
+
+fn inlined_description_and_display_and_cause() {
+	#[derive(Debug, ErrorChain)]
+	pub enum ErrorKind {
+		Msg(String),
+
+		#[error_chain(custom)]
+		#[error_chain(description = r#"|_| "http request returned an unsuccessful status code""#)]
+		#[error_chain(display = r#"|e| write!(f, "http request returned an unsuccessful status code: {}", e)"#)]
+		HttpStatus(u32),
+
+		#[error_chain(custom)]
+		#[error_chain(cause = "|_, err| err")]
+		FileIO(::std::path::PathBuf, ::std::io::Error),
+
+		#[error_chain(custom, cause = "file_io_error_cause")]
+		FileIO2(::std::path::PathBuf, ::std::io::Error),
+	}
+
+	fn file_io_error_cause<'a>(_: &::std::path::Path, err: &'a ::std::io::Error) -> &'a ::std::error::Error {
+		err
+	}
+
+	let err: Error = ErrorKind::HttpStatus(5).into();
+	assert_eq!("http request returned an unsuccessful status code", ::std::error::Error::description(&err));
+	assert_eq!("http request returned an unsuccessful status code: 5".to_string(), format!("{}", err));
+
+	let err: Error = ErrorKind::FileIO(::std::path::PathBuf::new(), ::std::io::Error::from_raw_os_error(1)).into();
+	assert!(::std::error::Error::cause(&err).is_some());
+
+	let err: Error = ErrorKind::FileIO2(::std::path::PathBuf::new(), ::std::io::Error::from_raw_os_error(1)).into();
+	assert!(::std::error::Error::cause(&err).is_some());
+}
+
+mod generics_test {
+	use std::{error, fmt, io};
+
+	mod inner1 {
+		use std::fmt;
+		#[derive(Debug, ErrorChain)]
+		pub enum ErrorKind<T: Send + fmt::Debug + 'static> {
+			Msg(String),
+
+			#[error_chain(custom)]
+			CustomGeneric(T)
+		}
+	}
+
+	mod inner2 {
+		#[derive(Debug, ErrorChain)]
+		pub enum ErrorKind {
+			Msg(String),
+		}
+	}
+
+	#[derive(Debug, ErrorChain)]
+	pub enum ErrorKind<T: error::Error + Send + 'static, U>
+		where U: Send + fmt::Debug + fmt::Display + 'static {
+		Msg(String),
+
+		#[error_chain(custom)]
+		#[error_chain(description = r#"|_| "custom error""#)]
+		#[error_chain(display = r#"|t| write!(f, "custom error: {}", t)"#)]
+		Custom(String),
+
+		#[error_chain(custom)]
+		#[error_chain(description = r#"|_| "custom generic error""#)]
+		#[error_chain(display = r#"|t| write!(f, "custom generic error: {}", t)"#)]
+		CustomGeneric(T),
+
+		#[error_chain(custom)]
+		#[error_chain(description = r#"|_| "custom generic boxed error""#)]
+		#[error_chain(display = r#"|t| write!(f, "custom generic boxed error: {}", t)"#)]
+		CustomGenericBoxed(Box<U>),
+
+		#[error_chain(link = "inner1::Error<U>")]
+		LinkGeneric(inner1::ErrorKind<U>),
+
+		#[error_chain(link = "inner2::Error")]
+		Link(inner2::ErrorKind),
+
+		#[error_chain(foreign)]
+		ForeignGeneric(T),
+
+		#[error_chain(foreign)]
+		ForeignGenericBoxed(Box<T>),
+
+		#[error_chain(foreign)]
+		Foreign(io::Error),
+	}
+}
+
+mod lifetimes_test {
+	use std::fmt;
+
+	#[derive(Debug)]
+	pub struct SomeError<'a>(&'a str);
+
+	impl<'a> ::std::error::Error for SomeError<'a> {
+		fn description(&self) -> &str { self.0 }
+	}
+
+	impl<'a> fmt::Display for SomeError<'a> {
+		fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result { f.write_str(self.0) }
+	}
+
+	#[derive(Debug, ErrorChain)]
+	pub enum ErrorKind<'a> {
+		Msg(String),
+
+		#[error_chain(foreign)]
+		Borrowed(SomeError<'a>),
+	}
+
+	pub fn borrowed_foreign_link() {
+		let s = "borrowed error".to_string();
+		let err: Error = SomeError(&s).into();
+		assert_eq!("borrowed error".to_string(), format!("{}", err));
+	}
+
+	pub fn chain_err() {
+		let result: Result<'static, ()> = Err(SomeError("inner")).chain_err(|| "outer");
+		assert_eq!(2, result.unwrap_err().source_chain().count());
+	}
+
+	pub fn borrowed_custom_link() {
+		mod borrowed {
+			#[derive(Debug, ErrorChain)]
+			pub enum ErrorKind<'a> {
+				Msg(String),
+
+				#[error_chain(custom)]
+				#[error_chain(display = r#"|token| write!(f, "invalid token {}", token)"#)]
+				InvalidToken(&'a str),
+			}
+		}
+
+		fn parse(input: &str) -> borrowed::Result<()> {
+			if input.starts_with('{') {
+				Ok(())
+			}
+			else {
+				Err(borrowed::ErrorKind::InvalidToken(input).into())
+			}
+		}
+
+		let input = "[1, 2]".to_string();
+		let err = parse(&input).unwrap_err();
+		match *err.kind() {
+			borrowed::ErrorKind::InvalidToken(token) => assert_eq!(token, "[1, 2]"),
+			_ => unreachable!(),
+		}
+		assert_eq!(err.to_string(), "invalid token [1, 2]");
+
+		// `backtrace()` doesn't require the error to be `'static`
+		let _ = err.backtrace();
+
+		let err: borrowed::Error = "msg".into();
+		assert_eq!(err.to_string(), "msg");
+	}
+
+	// The generated `From<&str>` impls add a lifetime of their own, which must not clash with the ErrorKind's
+	pub fn internal_lifetime_name() {
+		mod clash {
+			#[derive(Debug, ErrorChain)]
+			pub enum ErrorKind<'__a> {
+				Msg(String),
+
+				#[error_chain(custom)]
+				InvalidToken(&'__a str),
+			}
+		}
+
+		let input = "[".to_string();
+		let err: clash::Error = clash::ErrorKind::InvalidToken(&input).into();
+		match *err.kind() {
+			clash::ErrorKind::InvalidToken(token) => assert_eq!(token, "["),
+			_ => unreachable!(),
+		}
+
+		let err: clash::Error = "msg".into();
+		assert_eq!(err.to_string(), "msg");
+	}
+}
+
+fn test_without_msg_1() {
+	#[derive(Debug, ErrorChain)]
+	pub enum ErrorKind {
+		#[error_chain(custom)]
+		HttpStatus(u32),
+	}
+}
+
+fn test_without_msg_2() {
+	#[derive(Debug, ErrorChain)]
+	pub enum ErrorKind {
+		#[error_chain(custom)]
+		HttpStatus(u32),
+	}
+
+	// Should not conflict
 	#[cfg_attr(feature = "cargo-clippy", allow(fallible_impl_from))]
 	impl<'a> From<&'a str> for ErrorKind {
 		fn from(_: &'a str) -> Self {
 			unimplemented!()
 		}
-	}
+	}
+
+	#[cfg_attr(feature = "cargo-clippy", allow(fallible_impl_from))]
+	impl From<String> for ErrorKind {
+		fn from(_: String) -> Self {
+			unimplemented!()
+		}
+	}
+}
+
+fn macro_conflicts() {
+	#[derive(Debug, ErrorChain)]
+	#[error_chain(result = "MyResult")]
+	pub enum ErrorKind {
+		Msg(String),
+
+		#[error_chain(custom)]
+		Code(i32),
+	}
+
+	error_chain! {
+		types { ECError, ECErrorKind, ECResultExt, ECResult; }
+	}
+
+	quick_main!(|| -> MyResult<()> {
+		bail!("failed")
+	});
+
+	fn foo() -> MyResult<()> {
+		bail!("failed")
+	}
+
+	match foo() {
+		Ok(_) => unreachable!(),
+		Err(err) => match *err.kind() {
+			ErrorKind::Msg(ref s) if s == "failed" => (),
+			_ => unreachable!(),
+		},
+	}
+}
+
+mod need_not_be_pub {
+	#[derive(Debug, ErrorChain)]
+	enum ErrorKind {
+		Msg(String),
+	}
+}
+
+fn source_chain() {
+	#[derive(Debug, ErrorChain)]
+	pub enum ErrorKind {
+		Msg(String),
+	}
+
+	let io_error = ::std::io::Error::new(::std::io::ErrorKind::NotFound, "inner");
+	let err = Err::<(), _>(io_error).chain_err(|| "outer").unwrap_err();
+
+	let chain: Vec<_> = err.source_chain().map(ToString::to_string).collect();
+	assert_eq!(vec!["outer".to_string(), "inner".to_string()], chain);
+
+	// A foreign error's own source is followed too
+	#[derive(Debug)]
+	struct Wrapper(::std::io::Error);
+
+	impl ::std::fmt::Display for Wrapper {
+		fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result { write!(f, "wrapper") }
+	}
+
+	impl ::std::error::Error for Wrapper {
+		fn source(&self) -> Option<&(::std::error::Error + 'static)> { Some(&self.0) }
+	}
+
+	let wrapper = Wrapper(::std::io::Error::new(::std::io::ErrorKind::NotFound, "inner"));
+	let err = Err::<(), _>(wrapper).chain_err(|| "outer").unwrap_err();
+
+	let chain: Vec<_> = err.source_chain().map(ToString::to_string).collect();
+	assert_eq!(vec!["outer".to_string(), "wrapper".to_string(), "inner".to_string()], chain);
+}
+
+fn hygienic_generics() {
+	use std::fmt;
+
+	#[derive(Debug, ErrorChain)]
+	pub enum ErrorKind<__T: Send + fmt::Debug + 'static, __E: Send + fmt::Debug + 'static> {
+		Msg(String),
+
+		#[error_chain(custom)]
+		First(__T),
+
+		#[error_chain(custom)]
+		Second(__E),
+	}
+
+	let result: Result<u32, i32, ()> = Err(fmt::Error).chain_err(|| ErrorKind::First(5));
+	match *result.unwrap_err().kind() {
+		ErrorKind::First(5) => (),
+		_ => unreachable!(),
+	}
+}
+
+fn hygienic_into_foreign() {
+	use std::fmt;
+
+	#[derive(Debug, ErrorChain)]
+	pub enum ErrorKind<__T: Send + fmt::Debug + 'static> {
+		Msg(String),
+
+		#[error_chain(custom)]
+		First(__T),
+
+		#[error_chain(foreign)]
+		Fmt(fmt::Error),
+	}
+
+	let err: Error<u32> = fmt::Error.into();
+	let _: fmt::Error = err.into_foreign().unwrap();
+}
+
+fn hygienic_map_kind() {
+	use std::fmt;
+
+	#[derive(Debug, ErrorChain)]
+	pub enum ErrorKind<__F: Send + fmt::Debug + 'static> {
+		Msg(String),
+
+		#[error_chain(custom)]
+		First(__F),
+	}
+
+	let err: Error<u32> = ErrorKind::First(1).into();
+	let err = err.map_kind(|kind| match kind {
+		ErrorKind::First(value) => ErrorKind::First(value + 1),
+		kind => kind,
+	});
+	match *err.kind() {
+		ErrorKind::First(2) => (),
+		_ => unreachable!(),
+	}
+}
+
+fn error_partial_eq() {
+	#[derive(Debug, PartialEq, ErrorChain)]
+	#[error_chain(error_partial_eq)]
+	pub enum ErrorKind {
+		Msg(String),
+
+		#[error_chain(custom)]
+		Code(i32),
+	}
+
+	let result: Result<()> = Err(ErrorKind::Code(5).into());
+	assert_eq!(result.unwrap_err(), ErrorKind::Code(5).into());
+
+	// Only the kinds are compared
+	let chained = Error::with_chain(::std::fmt::Error, ErrorKind::Code(5));
+	assert_eq!(chained, ErrorKind::Code(5).into());
+	assert_ne!(chained, ErrorKind::Code(6).into());
+}
+
+mod module_test {
+	mod inner {
+		#[derive(Debug, ErrorChain)]
+		pub enum ErrorKind {
+			Msg(String),
+		}
+	}
+
+	#[derive(Debug, ErrorChain)]
+	#[error_chain(module = "errors", prelude = "prelude", bail = "bail_module_test")]
+	pub enum ErrorKind {
+		Msg(String),
+
+		#[error_chain(link = "inner::Error")]
+		Inner(inner::ErrorKind),
+
+		#[error_chain(foreign)]
+		Io(::std::io::Error),
+	}
+
+	// Items named like the generated ones don't clash with them
+	pub struct Error;
+	pub type Result = ();
+
+	mod private_errors {
+		#[derive(Debug, ErrorChain)]
+		#[error_chain(module = "errors")]
+		enum ErrorKind {
+			Msg(String),
+		}
+
+		pub fn private() {
+			let err: errors::Error = "private".into();
+			match *err.kind() {
+				errors::ErrorKind::Msg(ref s) if s == "private" => (),
+				_ => unreachable!(),
+			}
+		}
+	}
+
+	pub fn public() {
+		use self::errors::ResultExt;
+
+		fn read() -> errors::Result<()> {
+			Err(::std::io::Error::new(::std::io::ErrorKind::NotFound, "file not found")).chain_err(|| "could not read")
+		}
+
+		fn check(value: i32) -> errors::Result<()> {
+			use self::errors::{ Error, ErrorKind };
+
+			if value < 0 {
+				bail_module_test!("value {} is negative", value);
+			}
+
+			Ok(())
+		}
+
+		let err = read().unwrap_err();
+		assert_eq!(err.to_string(), "could not read");
+		match *err.kind() {
+			errors::ErrorKind::Msg(_) => (),
+			_ => unreachable!(),
+		}
+
+		let inner_err: inner::Error = "inner".into();
+		let err: errors::Error = inner_err.into();
+		match *err.kind() {
+			ErrorKind::Inner(_) => (),
+			_ => unreachable!(),
+		}
+
+		let _: errors::prelude::Error = "prelude".into();
+
+		assert_eq!(check(-1).unwrap_err().to_string(), "value -1 is negative");
+	}
+
+	pub fn private() {
+		private_errors::private();
+	}
+}
+
+mod prelude_test {
+	mod errors {
+		#[derive(Debug, ErrorChain)]
+		#[error_chain(prelude = "prelude")]
+		pub enum ErrorKind {
+			Msg(String),
+
+			#[error_chain(custom)]
+			Code(i32),
+		}
+	}
+
+	mod private_errors {
+		#[derive(Debug, ErrorChain)]
+		#[error_chain(result = "", prelude = "prelude")]
+		enum ErrorKind {
+			Msg(String),
+		}
+
+		pub fn private() {
+			use self::prelude::*;
+
+			let _: ::std::result::Result<(), Error> = Err(::std::fmt::Error).chain_err(|| ErrorKind::Msg("foo".to_string()));
+		}
+	}
+
+	pub fn public() {
+		use self::errors::prelude::*;
+
+		let result: Result<()> = Err(ErrorKind::Code(5).into());
+		let _: Result<()> = result.chain_err(|| "foo");
+	}
+
+	pub use self::private_errors::private;
+}
+
+fn context() {
+	#[derive(Debug, ErrorChain)]
+	pub enum ErrorKind {
+		Msg(String),
+
+		#[error_chain(foreign)]
+		Io(::std::io::Error),
+	}
+
+	let err: Error = ::std::io::Error::new(::std::io::ErrorKind::NotFound, "file not found").into();
+	let err = err.context(format_args!("could not open {}", "foo.txt"));
+
+	assert_eq!(err.to_string(), "could not open foo.txt");
+
+	let source = ::std::error::Error::source(&err).unwrap();
+	assert_eq!(source.to_string(), "file not found");
+	assert!(source.downcast_ref::<Error>().is_some());
+}
+
+fn location() {
+	#[derive(Debug, ErrorChain)]
+	#[error_chain(location)]
+	pub enum ErrorKind {
+		Msg(String),
+
+		#[error_chain(foreign)]
+		Io(::std::io::Error),
+	}
+
+	fn try_io() -> Result<()> {
+		Err(::std::io::Error::new(::std::io::ErrorKind::NotFound, "file not found"))?;
+		Ok(())
+	}
+
+	let line = line!() - 4;
+	let err = try_io().unwrap_err();
+	assert_eq!(err.location().file(), file!());
+	assert_eq!(err.location().line(), line);
+
+	let err = Error::from_kind("Test".into()); let line = line!();
+	assert_eq!(err.location().line(), line);
+
+	let err = Err::<(), _>(err).chain_err(|| "Result chained"); let line = line!();
+	assert_eq!(err.unwrap_err().location().line(), line);
+
+	let err = None::<()>.chain_err(|| "Option chained"); let line = line!();
+	assert_eq!(err.unwrap_err().location().line(), line);
+}
+
+fn empty_error_kind() {
+	#[derive(Debug, ErrorChain)]
+	pub enum ErrorKind { }
+
+	fn never_fails() -> Result<u32> {
+		Ok(5)
+	}
+
+	assert_eq!(never_fails().unwrap(), 5);
+	assert!(Some(5).chain_err(|| -> ErrorKind { unreachable!() }).is_ok());
+}
+
+fn from_boxed() {
+	#[derive(Debug, ErrorChain)]
+	#[error_chain(from_boxed)]
+	pub enum ErrorKind {
+		Msg(String),
+	}
+
+	let boxed: Box<::std::error::Error + Send + Sync> = Box::new(::std::io::Error::new(::std::io::ErrorKind::NotFound, "file not found"));
+	let err: Error = boxed.into();
+
+	match *err.kind() {
+		ErrorKind::Msg(ref message) => assert_eq!(message, "file not found"),
+	}
+	assert_eq!(err.to_string(), "file not found");
+
+	let source = ::std::error::Error::source(&err).unwrap();
+	assert!(source.downcast_ref::<::std::io::Error>().is_some());
+}
+
+fn kind_name() {
+	mod inner {
+		#[derive(Debug, ErrorChain)]
+		pub enum ErrorKind {
+			Msg(String),
+		}
+	}
+
+	#[derive(Debug, ErrorChain)]
+	pub enum ErrorKind {
+		Msg(String),
+
+		#[error_chain(link = "inner::Error")]
+		Inner(inner::ErrorKind),
+
+		#[error_chain(foreign)]
+		Io(::std::io::Error),
+
+		#[error_chain(custom, description = r#"|_| "HTTP request returned an unsuccessful status code""#)]
+		HttpStatus { code: u32 },
+
+		#[error_chain(custom)]
+		Unknown,
+	}
+
+	assert_eq!(ErrorKind::Msg("Test".to_string()).kind_name(), "Msg");
+	assert_eq!(ErrorKind::Inner(inner::ErrorKind::Msg("Test".to_string())).kind_name(), "Inner");
+	assert_eq!(ErrorKind::Io(::std::io::Error::new(::std::io::ErrorKind::NotFound, "file not found")).kind_name(), "Io");
+	assert_eq!(ErrorKind::HttpStatus { code: 404 }.kind_name(), "HttpStatus");
+	assert_eq!(ErrorKind::Unknown.kind_name(), "Unknown");
+
+	let err: Error = ErrorKind::HttpStatus { code: 404 }.into();
+	assert_eq!(err.kind_name(), "HttpStatus");
+}
+
+mod raw_identifiers {
+	mod r#match {
+		#[derive(Debug, ErrorChain)]
+		pub enum ErrorKind {
+			Msg(String),
+		}
+	}
+
+	#[derive(Debug, ErrorChain)]
+	#[error_chain(error = "r#Error", result_ext = "r#ResultExt", result = "r#type")]
+	pub enum ErrorKind {
+		Msg(String),
+
+		#[error_chain(link = "r#match::Error")]
+		r#Match(r#match::ErrorKind),
+
+		#[error_chain(custom)]
+		r#Custom,
+	}
+
+	pub fn test() {
+		fn inner() -> r#match::Result<()> {
+			Err("Test".into())
+		}
+
+		fn outer() -> r#type<()> {
+			inner()?;
+			Ok(())
+		}
+
+		let err = outer().unwrap_err();
+		assert_eq!(err.to_string(), "Test");
+		assert_eq!(err.kind_name(), "Match");
+
+		let err: r#Error = ErrorKind::r#Custom.into();
+		assert_eq!(err.to_string(), "Custom");
+		assert_eq!(err.kind_name(), "Custom");
+	}
+}
+
+fn chain_iter_kinds() {
+	mod inner {
+		#[derive(Debug, ErrorChain)]
+		pub enum ErrorKind {
+			Msg(String),
+		}
+	}
+
+	#[derive(Debug, ErrorChain)]
+	pub enum ErrorKind {
+		Msg(String),
+
+		#[error_chain(link = "inner::Error")]
+		Inner(inner::ErrorKind),
+
+		#[error_chain(custom)]
+		Code(i32),
+	}
+
+	let err: inner::Error = "inner".into();
+	let err: Error = err.into();
+	let err = Error::with_chain(::std::io::Error::new(::std::io::ErrorKind::NotFound, "file not found"), err).chain_err(|| ErrorKind::Code(5));
+
+	let kinds: Vec<_> = err.chain_iter_kinds().map(ErrorKind::kind_name).collect();
+	assert_eq!(kinds, ["Code", "Inner"]);
+}
+
+fn no_from() {
+	#[derive(Debug, ErrorChain)]
+	#[error_chain(no_from)]
+	pub enum ErrorKind {
+		Msg(String),
+
+		#[error_chain(foreign)]
+		Read(::std::io::Error),
+
+		#[error_chain(foreign)]
+		Write(::std::io::Error),
+	}
+
+	fn write() -> Result<()> {
+		Err(::std::io::Error::new(::std::io::ErrorKind::WriteZero, "disk full")).map_err(|err| ErrorKind::Write(err).into())
+	}
+
+	let err = write().unwrap_err();
+	assert_eq!(err.to_string(), "disk full");
+	assert_eq!(err.kind_name(), "Write");
+
+	let err: Error = write().chain_err(|| ErrorKind::Msg("Could not save".to_string())).unwrap_err();
+	assert_eq!(err.to_string(), "Could not save");
+}
+
+fn bail() {
+	#[derive(Debug, ErrorChain)]
+	#[error_chain(bail = "bail_myerror")]
+	pub enum ErrorKind {
+		Msg(String),
+	}
+
+	fn check(value: i32) -> Result<()> {
+		if value < 0 {
+			bail_myerror!("value {} is negative", value);
+		}
+
+		Ok(())
+	}
+
+	assert!(check(5).is_ok());
+
+	let err = check(-5).unwrap_err();
+	assert_eq!(err.to_string(), "value -5 is negative");
+	match *err.kind() {
+		ErrorKind::Msg(ref s) if s == "value -5 is negative" => (),
+		_ => unreachable!(),
+	}
+}
+
+fn fallback_description() {
+	#[derive(Debug, ErrorChain)]
+	#[error_chain(description = "kind_description")]
+	pub enum ErrorKind {
+		Msg(String),
+
+		#[error_chain(foreign)]
+		Io(::std::io::Error),
+
+		#[error_chain(custom)]
+		Named { name: String },
+
+		#[error_chain(custom, description = r#"|| "timed out""#)]
+		Timeout,
+	}
+
+	fn kind_description(kind: &ErrorKind) -> &str {
+		match *kind {
+			ErrorKind::Named { ref name } => name,
+			_ => "generic error",
+		}
+	}
+
+	assert_eq!(ErrorKind::Msg("foo".to_string()).description(), "foo");
+	assert_eq!(ErrorKind::Io(::std::io::Error::new(::std::io::ErrorKind::NotFound, "file not found")).description(), "generic error");
+	assert_eq!(ErrorKind::Named { name: "bar".to_string() }.description(), "bar");
+	assert_eq!(ErrorKind::Named { name: "bar".to_string() }.to_string(), "bar");
+	assert_eq!(ErrorKind::Timeout.description(), "timed out");
+}
+
+fn boxed_foreign_link() {
+	#[derive(Debug)]
+	struct ConfigError(::std::io::Error);
+
+	impl ::std::fmt::Display for ConfigError {
+		fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+			f.write_str("invalid config")
+		}
+	}
+
+	impl ::std::error::Error for ConfigError {
+		fn description(&self) -> &str { "invalid config" }
+		fn cause(&self) -> Option<&::std::error::Error> { Some(&self.0) }
+	}
+
+	#[derive(Debug, ErrorChain)]
+	pub enum ErrorKind {
+		Msg(String),
+
+		#[error_chain(foreign)]
+		Other(Box<::std::error::Error + Send + Sync>),
+	}
+
+	let boxed: Box<::std::error::Error + Send + Sync> = Box::new(ConfigError(::std::io::Error::new(::std::io::ErrorKind::NotFound, "file not found")));
+	let err: Error = boxed.into();
+	assert_eq!(err.to_string(), "invalid config");
+	assert_eq!(::std::error::Error::description(&err), "invalid config");
+	assert_eq!(::std::error::Error::cause(&err).unwrap().to_string(), "file not found");
+}
+
+fn chain_err_name() {
+	#[derive(Debug, ErrorChain)]
+	#[error_chain(chain_err = "with_ctx")]
+	pub enum ErrorKind {
+		Msg(String),
+	}
+
+	trait OtherResultExt {
+		fn chain_err(self) -> Self;
+	}
+
+	impl<T, E> OtherResultExt for ::std::result::Result<T, E> {
+		fn chain_err(self) -> Self { self }
+	}
+
+	let result: ::std::result::Result<(), ::std::io::Error> = Err(::std::io::Error::new(::std::io::ErrorKind::NotFound, "file not found"));
+	let result = result.chain_err();
+	let err: Error = result.with_ctx(|| "Could not read config").unwrap_err();
+	assert_eq!(err.to_string(), "Could not read config");
+
+	let err = None::<()>.with_ctx(|| "Missing value").unwrap_err();
+	assert_eq!(err.to_string(), "Missing value");
+
+	let err = err.with_ctx(|| "Outer");
+	assert_eq!(err.to_string(), "Outer");
+	assert_eq!(err.iter().count(), 2);
+}
+
+fn discriminant_code() {
+	#[derive(Debug, ErrorChain)]
+	pub enum ErrorKind {
+		#[error_chain(custom)]
+		FileNotFound = 404,
+
+		#[error_chain(custom)]
+		Internal = 500,
+
+		#[error_chain(custom)]
+		Unknown,
+	}
+
+	assert_eq!(ErrorKind::FileNotFound.code(), Some(404));
+	assert_eq!(ErrorKind::Internal.code(), Some(500));
+	assert_eq!(ErrorKind::Unknown.code(), None);
+
+	let err: Error = ErrorKind::FileNotFound.into();
+	assert_eq!(err.code(), Some(404));
+}
+
+fn alternate_display() {
+	#[derive(Debug, ErrorChain)]
+	pub enum ErrorKind {
+		Msg(String),
+	}
+
+	let err = Error::with_chain(::std::io::Error::new(::std::io::ErrorKind::NotFound, "file not found"), "Could not read config");
+	let err = err.chain_err(|| "Could not start");
+
+	assert_eq!(format!("{}", err), "Could not start");
+	assert_eq!(format!("{:#}", err), "Could not start: caused by: Could not read config: caused by: file not found");
+
+	let err: Error = "Single".into();
+	assert_eq!(format!("{:#}", err), "Single");
+
+	// The alternate flag isn't passed on to the kind or the errors in the chain
+	mod flagged {
+		#[derive(Debug)]
+		pub struct Flagged;
+
+		impl ::std::fmt::Display for Flagged {
+			fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+				f.write_str(if f.alternate() { "alternate" } else { "flagged" })
+			}
+		}
+
+		impl ::std::error::Error for Flagged { }
+
+		#[derive(Debug, ErrorChain)]
+		pub enum ErrorKind {
+			Msg(String),
+
+			#[error_chain(foreign)]
+			Flagged(Flagged),
+		}
+	}
+
+	let err: flagged::Error = flagged::Flagged.into();
+	assert_eq!(format!("{:#}", err), "flagged");
+
+	let err = Error::with_chain(flagged::Flagged, "Could not start");
+	assert_eq!(format!("{:#}", err), "Could not start: caused by: flagged");
+}
+
+#[deny(deprecated)]
+fn no_error_description() {
+	#[derive(Debug, ErrorChain)]
+	#[error_chain(no_error_description)]
+	pub enum ErrorKind {
+		Msg(String),
+
+		#[error_chain(custom, description = r#"|| "HTTP request failed""#)]
+		Http,
+	}
+
+	let err: Error = ErrorKind::Http.into();
+	assert_eq!(err.to_string(), "HTTP request failed");
+	assert_eq!(err.kind().description(), "HTTP request failed");
+
+	// The `Error` falls back to the default `description()` instead of forwarding to the kind
+	#[allow(deprecated)]
+	let description = ::std::error::Error::description(&err).to_string();
+	assert_ne!(description, "HTTP request failed");
+}
+
+fn source_field() {
+	#[derive(Debug, ErrorChain)]
+	pub enum ErrorKind {
+		Msg(String),
+
+		#[error_chain(custom)]
+		Request { url: String, #[error_chain(source)] err: ::std::io::Error },
+
+		#[error_chain(custom)]
+		FileIO(::std::path::PathBuf, #[error_chain(source)] ::std::io::Error),
+
+		#[error_chain(custom)]
+		Http(u32),
+	}
+
+	let err: Error = (ErrorKind::Request { url: "http://example.com".to_string(), err: ::std::io::Error::new(::std::io::ErrorKind::Other, "connection reset") }).into();
+	assert_eq!(::std::error::Error::cause(&err).unwrap().to_string(), "connection reset");
+	assert_eq!(::std::error::Error::source(&err).unwrap().to_string(), "connection reset");
+	assert!(::std::error::Error::source(&err).unwrap().downcast_ref::<::std::io::Error>().is_some());
+
+	let err: Error = ErrorKind::FileIO(::std::path::PathBuf::new(), ::std::io::Error::new(::std::io::ErrorKind::NotFound, "file not found")).into();
+	assert_eq!(::std::error::Error::cause(&err).unwrap().to_string(), "file not found");
+	assert_eq!(::std::error::Error::source(&err).unwrap().to_string(), "file not found");
+
+	let err: Error = ErrorKind::Http(404).into();
+	assert!(::std::error::Error::cause(&err).is_none());
+	assert!(::std::error::Error::source(&err).is_none());
+}
+
+fn result_error() {
+	#[derive(Debug, ErrorChain)]
+	#[error_chain(result = "IoResult", result_error = "Error<::std::io::Error>")]
+	pub enum ErrorKind<T: ::std::fmt::Debug + Send + 'static> {
+		Msg(String),
+
+		#[error_chain(custom)]
+		Inner(T),
+	}
+
+	fn fails() -> IoResult<()> {
+		Err(ErrorKind::Inner(::std::io::Error::new(::std::io::ErrorKind::NotFound, "file not found")).into())
+	}
+
+	match *fails().unwrap_err().kind() {
+		ErrorKind::Inner(ref err) => assert_eq!(err.kind(), ::std::io::ErrorKind::NotFound),
+		_ => unreachable!(),
+	}
+
+	mod with_result_default {
+		#[derive(Debug, ErrorChain)]
+		#[error_chain(result_default, result_error = "Error<u32>")]
+		pub enum ErrorKind<T: ::std::fmt::Debug + Send + 'static> {
+			Msg(String),
+
+			#[error_chain(custom)]
+			Inner(T),
+		}
+	}
+
+	fn fails_with_code() -> with_result_default::Result<()> {
+		Err(with_result_default::ErrorKind::Inner(5).into())
+	}
+
+	fn fails_with_string() -> with_result_default::Result<(), with_result_default::Error<String>> {
+		Err(with_result_default::ErrorKind::Inner("bad value".to_string()).into())
+	}
+
+	match *fails_with_code().unwrap_err().kind() {
+		with_result_default::ErrorKind::Inner(5) => (),
+		_ => unreachable!(),
+	}
+
+	match *fails_with_string().unwrap_err().kind() {
+		with_result_default::ErrorKind::Inner(ref s) if s == "bad value" => (),
+		_ => unreachable!(),
+	}
+}
+
+fn closures_with_context() {
+	const PREFIX: &str = "request failed";
+
+	fn status_reason(code: u32) -> &'static str {
+		match code {
+			404 => "Not Found",
+			_ => "Unknown",
+		}
+	}
+
+	#[derive(Debug, ErrorChain)]
+	pub enum ErrorKind {
+		Msg(String),
+
+		#[error_chain(custom)]
+		#[error_chain(description = r#"|_| PREFIX"#)]
+		#[error_chain(display = r#"|code| write!(f, "{}: {} {}", PREFIX, code, status_reason(*code))"#)]
+		HttpStatus(u32),
+
+		#[error_chain(custom)]
+		#[error_chain(display = r#"|url, code| {
+			let reason = status_reason(*code);
+			if reason == "Unknown" {
+				write!(f, "{}: {} returned {}", PREFIX, url, code)
+			}
+			else {
+				write!(f, "{}: {} returned {} {}", PREFIX, url, code, reason)
+			}
+		}"#)]
+		#[error_chain(cause = r#"|_, _| {
+			let _ = PREFIX;
+			&IO_ERROR_PLACEHOLDER
+		}"#)]
+		Redirect(String, u32),
+	}
+
+	struct Placeholder;
+
+	impl ::std::fmt::Debug for Placeholder {
+		fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+			f.write_str("Placeholder")
+		}
+	}
+
+	impl ::std::fmt::Display for Placeholder {
+		fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+			f.write_str("placeholder cause")
+		}
+	}
+
+	impl ::std::error::Error for Placeholder {
+		fn description(&self) -> &str { "placeholder cause" }
+	}
+
+	static IO_ERROR_PLACEHOLDER: Placeholder = Placeholder;
+
+	let err: Error = ErrorKind::HttpStatus(404).into();
+	assert_eq!(::std::error::Error::description(&err), "request failed");
+	assert_eq!(err.to_string(), "request failed: 404 Not Found");
+
+	let err: Error = ErrorKind::Redirect("http://example.com".to_string(), 404).into();
+	assert_eq!(err.to_string(), "request failed: http://example.com returned 404 Not Found");
+	assert_eq!(::std::error::Error::cause(&err).unwrap().to_string(), "placeholder cause");
+
+	let err: Error = ErrorKind::Redirect("http://example.com".to_string(), 302).into();
+	assert_eq!(err.to_string(), "request failed: http://example.com returned 302");
+}
+
+fn closure_calls_outer_call_fn() {
+	fn call(code: &u32) -> u32 {
+		code + 1
+	}
+
+	#[derive(Debug, ErrorChain)]
+	pub enum ErrorKind {
+		Msg(String),
+
+		#[error_chain(custom)]
+		#[error_chain(description = r#"|code| if call(code) > 0 { "code" } else { "zero" }"#)]
+		#[error_chain(display = r#"|code| write!(f, "{}", call(code))"#)]
+		Code(u32),
+	}
+
+	let err: Error = ErrorKind::Code(5).into();
+	assert_eq!(::std::error::Error::description(&err), "code");
+	assert_eq!(err.to_string(), "6");
+}
+
+fn standard_derives() {
+	#[derive(Clone, Debug, PartialEq, Eq, Hash, ErrorChain)]
+	pub enum ErrorKind {
+		Msg(String),
+
+		#[error_chain(custom)]
+		HttpStatus(u32),
+
+		#[error_chain(custom)]
+		Request { url: String, status: u32 },
+	}
+
+	let kind = ErrorKind::Request { url: "http://example.com".to_string(), status: 404 };
+	let cloned = kind.clone();
+	assert_eq!(kind, cloned);
+
+	let mut set = ::std::collections::HashSet::new();
+	set.insert(kind);
+	set.insert(cloned);
+	set.insert(ErrorKind::HttpStatus(404));
+	assert_eq!(set.len(), 2);
+
+	let err: Error = ErrorKind::HttpStatus(404).into();
+	assert!(set.contains(&err.kind().clone()));
+}
+
+fn predicates() {
+	#[derive(Debug, ErrorChain)]
+	#[error_chain(predicates)]
+	pub enum ErrorKind {
+		Msg(String),
+
+		#[error_chain(foreign)]
+		Io(::std::io::Error),
+
+		#[error_chain(custom)]
+		Test,
+
+		#[error_chain(custom)]
+		NotFound { path: String },
+
+		#[error_chain(custom)]
+		HTTPStatus(u32),
+	}
+
+	assert!(Error::from_kind(ErrorKind::Test).is_test());
+	assert!(!Error::from_kind(ErrorKind::Test).is_not_found());
+
+	let err: Error = (ErrorKind::NotFound { path: "foo".to_string() }).into();
+	assert!(err.is_not_found());
+	assert!(err.kind().is_not_found());
+	assert!(!err.is_msg());
+
+	let err: Error = ErrorKind::HTTPStatus(404).into();
+	assert!(err.is_http_status());
+
+	let err: Error = "failed".into();
+	assert!(err.is_msg());
+
+	let err: Error = ::std::io::Error::from_raw_os_error(1).into();
+	assert!(err.is_io());
+}
+
+fn run() {
+	#[derive(Debug, ErrorChain)]
+	#[error_chain(run)]
+	pub enum ErrorKind {
+		Msg(String),
+	}
+
+	assert_eq!(run(|| Ok(())), 0);
+	assert_eq!(run(|| Err("failed".into())), 1);
+
+	mod generic {
+		#[derive(Debug, ErrorChain)]
+		#[error_chain(run)]
+		pub enum ErrorKind<T: ::std::fmt::Debug + ::std::fmt::Display + Send + 'static = u32> {
+			Msg(String),
+
+			#[error_chain(custom, display = r#"|value| write!(f, "bad value {}", value)"#)]
+			Value(T),
+		}
+	}
+
+	assert_eq!(generic::run(|| -> generic::Result<()> { Ok(()) }), 0);
+	assert_eq!(generic::run(|| Err(generic::ErrorKind::Value("foo").into())), 1);
+}
+
+fn foreign_default_fields() {
+	#[derive(Debug, ErrorChain)]
+	pub enum ErrorKind {
+		Msg(String),
+
+		#[error_chain(foreign)]
+		Io { #[error_chain(default)] path: Option<String>, source: ::std::io::Error, #[error_chain(default)] retries: u32 },
+
+		#[error_chain(foreign)]
+		Fmt(#[error_chain(default)] u32, ::std::fmt::Error),
+	}
+
+	let err: Error = ::std::io::Error::new(::std::io::ErrorKind::NotFound, "file not found").into();
+	match *err.kind() {
+		ErrorKind::Io { path: None, ref source, retries: 0 } => assert_eq!(source.kind(), ::std::io::ErrorKind::NotFound),
+		_ => unreachable!(),
+	}
+	assert_eq!(err.to_string(), "file not found");
+
+	let err: Error = ::std::fmt::Error.into();
+	match *err.kind() {
+		ErrorKind::Fmt(0, _) => (),
+		_ => unreachable!(),
+	}
+	assert_eq!(err.to_string(), ::std::fmt::Error.to_string());
+}
+
+fn take_source() {
+	#[derive(Debug, ErrorChain)]
+	pub enum ErrorKind {
+		Msg(String),
+	}
+
+	let err = Error::with_chain(::std::io::Error::new(::std::io::ErrorKind::NotFound, "file not found"), "could not read config");
+	let source = err.into_source().unwrap();
+	assert_eq!(source.to_string(), "file not found");
+	assert_eq!(source.downcast_ref::<::std::io::Error>().unwrap().kind(), ::std::io::ErrorKind::NotFound);
+
+	let mut err = Error::with_chain(::std::io::Error::new(::std::io::ErrorKind::NotFound, "file not found"), "could not read config");
+	assert_eq!(err.take_source().unwrap().to_string(), "file not found");
+	assert!(err.take_source().is_none());
+	assert!(::std::error::Error::source(&err).is_none());
+	assert_eq!(err.to_string(), "could not read config");
+
+	let err: Error = "no chain".into();
+	assert!(err.into_source().is_none());
+}
+
+fn box_str_msg() {
+	#[derive(Debug, ErrorChain)]
+	#[error_chain(result = "Result")]
+	pub enum ErrorKind {
+		Msg(Box<str>),
+	}
+
+	let err: Error = "from str".into();
+	assert_eq!(err.to_string(), "from str");
+	assert_eq!(::std::error::Error::description(&err), "from str");
+
+	let err: Error = "from string".to_string().into();
+	match *err.kind() {
+		ErrorKind::Msg(ref s) => assert_eq!(&**s, "from string"),
+	}
+
+	let err = err.context("with context");
+	assert_eq!(err.to_string(), "with context");
+
+	fn foo() -> Result<()> {
+		bail!("bailed with {}", 5)
+	}
+
+	assert_eq!(foo().unwrap_err().to_string(), "bailed with 5");
+}
+
+fn foreign_source() {
+	#[derive(Debug)]
+	struct Inner;
+
+	impl ::std::fmt::Display for Inner {
+		fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result { f.write_str("inner") }
+	}
+
+	impl ::std::error::Error for Inner { }
+
+	#[derive(Debug)]
+	struct Outer(Inner);
+
+	impl ::std::fmt::Display for Outer {
+		fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result { f.write_str("outer") }
+	}
+
+	impl ::std::error::Error for Outer {
+		fn source(&self) -> Option<&(::std::error::Error + 'static)> { Some(&self.0) }
+	}
+
+	#[derive(Debug, ErrorChain)]
+	pub enum ErrorKind {
+		Msg(String),
+
+		#[error_chain(foreign)]
+		Outer(Outer),
+	}
+
+	let err: Error = Outer(Inner).into();
+	assert_eq!(::std::error::Error::source(&err).unwrap().to_string(), "inner");
+	assert_eq!(::std::error::Error::cause(&err).unwrap().to_string(), "inner");
+
+	let chain: Vec<_> = err.source_chain().map(|err| err.to_string()).collect();
+	assert_eq!(chain, ["outer", "inner"]);
+	assert_eq!(err.iter().count(), 2);
+}
+
+fn error_repr() {
+	#[derive(Debug, ErrorChain)]
+	#[error_chain(error_repr = "C")]
+	#[repr(u8)]
+	pub enum ErrorKind {
+		Msg(String),
+
+		#[error_chain(custom)]
+		Code(i32),
+	}
+
+	let err: Error = ErrorKind::Code(5).into();
+	match *err.kind() {
+		ErrorKind::Code(5) => (),
+		_ => unreachable!(),
+	}
+}
+
+fn renamed_inner_link() {
+	mod inner {
+		#[derive(Debug, ErrorChain)]
+		#[error_chain(error = "Inner", result_ext = "InnerExt", result = "InnerResult")]
+		pub enum ErrorKind {
+			Msg(String),
+		}
+	}
+
+	#[derive(Debug, ErrorChain)]
+	pub enum ErrorKind {
+		Msg(String),
+
+		#[error_chain(link = "inner::Inner")]
+		Inner(inner::ErrorKind),
+	}
+
+	fn inner_fn() -> inner::InnerResult<()> {
+		let result: ::std::result::Result<(), ::std::io::Error> = Err(::std::io::Error::new(::std::io::ErrorKind::NotFound, "file not found"));
+		inner::InnerExt::chain_err(result, || "inner failed")
+	}
+
+	fn outer_fn() -> Result<()> {
+		inner_fn()?;
+		Ok(())
+	}
+
+	let err = outer_fn().unwrap_err();
+	match *err.kind() {
+		ErrorKind::Inner(inner::ErrorKind::Msg(ref s)) if s == "inner failed" => (),
+		_ => unreachable!(),
+	}
+	assert_eq!(::std::error::Error::source(&err).unwrap().to_string(), "file not found");
+
+	let err: Error = ResultExt::chain_err(inner_fn(), || "outer failed").unwrap_err();
+	assert_eq!(err.to_string(), "outer failed");
+	assert_eq!(err.iter().count(), 3);
+}
+
+fn map_kind() {
+	#[derive(Debug, ErrorChain)]
+	#[error_chain(backtrace_env = "DERIVE_ERROR_CHAIN_TESTS_MAP_KIND_BACKTRACE")]
+	pub enum ErrorKind {
+		Msg(String),
+
+		#[error_chain(custom)]
+		Code(i32),
+	}
+
+	::std::env::set_var("DERIVE_ERROR_CHAIN_TESTS_MAP_KIND_BACKTRACE", "1");
+	let err: Error = ErrorKind::Code(1).into();
+	::std::env::remove_var("DERIVE_ERROR_CHAIN_TESTS_MAP_KIND_BACKTRACE");
+	assert!(err.backtrace().is_some());
+	let backtrace = err.backtrace().unwrap() as *const _;
+
+	let err = err.map_kind(|kind| match kind {
+		ErrorKind::Code(code) => ErrorKind::Code(code + 1),
+		kind => kind,
+	});
+	match *err.kind() {
+		ErrorKind::Code(2) => (),
+		_ => unreachable!(),
+	}
+	assert_eq!(err.backtrace().unwrap() as *const _, backtrace);
+}
+
+fn source_field_index() {
+	#[derive(Debug, ErrorChain)]
+	pub enum ErrorKind {
+		Msg(String),
+
+		#[error_chain(custom, source_field = 0)]
+		Wrapped(::std::io::Error),
+
+		#[error_chain(custom)]
+		#[error_chain(source_field = 1)]
+		Request(String, ::std::io::Error),
+	}
+
+	let err: Error = ErrorKind::Wrapped(::std::io::Error::new(::std::io::ErrorKind::NotFound, "file not found")).into();
+	assert_eq!(::std::error::Error::source(&err).unwrap().to_string(), "file not found");
+	assert_eq!(::std::error::Error::cause(&err).unwrap().to_string(), "file not found");
+
+	let err: Error = ErrorKind::Request("http://example.com".to_string(), ::std::io::Error::new(::std::io::ErrorKind::TimedOut, "timed out")).into();
+	assert_eq!(::std::error::Error::source(&err).unwrap().to_string(), "timed out");
+}
+
+fn into_io() {
+	#[derive(Debug, ErrorChain)]
+	#[error_chain(into_io)]
+	pub enum ErrorKind {
+		Msg(String),
+	}
+
+	fn read_config() -> ::std::io::Result<()> {
+		let result: Result<()> = Err("could not read config".into());
+		result?;
+		Ok(())
+	}
+
+	let err = read_config().unwrap_err();
+	assert_eq!(err.kind(), ::std::io::ErrorKind::Other);
+	assert_eq!(err.to_string(), "could not read config");
+
+	let err: ::std::io::Error = Error::with_chain(::std::io::Error::new(::std::io::ErrorKind::NotFound, "file not found"), "could not read config").into();
+	assert_eq!(::std::error::Error::source(err.get_ref().unwrap()).unwrap().to_string(), "file not found");
+}
+
+fn path_description_and_display() {
+	mod fmt {
+		pub fn code<T: ::std::fmt::Display>(f: &mut ::std::fmt::Formatter, code: &T) -> ::std::fmt::Result {
+			write!(f, "code {}", code)
+		}
+
+		pub fn description<T>(_: &T) -> &'static str {
+			"code"
+		}
+	}
+
+	trait Describe {
+		fn describe(code: &u32) -> &'static str;
+		fn display(f: &mut ::std::fmt::Formatter, code: &u32) -> ::std::fmt::Result;
+	}
+
+	struct Describer;
+
+	impl Describe for Describer {
+		fn describe(_: &u32) -> &'static str {
+			"described code"
+		}
+
+		fn display(f: &mut ::std::fmt::Formatter, code: &u32) -> ::std::fmt::Result {
+			write!(f, "described code {}", code)
+		}
+	}
+
+	#[derive(Debug, ErrorChain)]
+	pub enum ErrorKind {
+		Msg(String),
+
+		#[error_chain(custom)]
+		#[error_chain(description = "fmt::description::<u32>")]
+		#[error_chain(display = "fmt::code::<u32>")]
+		Turbofish(u32),
+
+		#[error_chain(custom)]
+		#[error_chain(description = "<Describer as Describe>::describe")]
+		#[error_chain(display = "<Describer as Describe>::display")]
+		QualifiedSelf(u32),
+	}
+
+	let err: Error = ErrorKind::Turbofish(5).into();
+	assert_eq!(::std::error::Error::description(&err), "code");
+	assert_eq!(err.to_string(), "code 5");
+
+	let err: Error = ErrorKind::QualifiedSelf(5).into();
+	assert_eq!(::std::error::Error::description(&err), "described code");
+	assert_eq!(err.to_string(), "described code 5");
+}
+
+fn error_debug() {
+	pub struct Handle(u32);
+
+	#[derive(ErrorChain)]
+	#[error_chain(error_debug = "debug_error")]
+	pub enum ErrorKind {
+		Msg(String),
+
+		#[error_chain(custom, display = r#"|handle: &Handle| write!(f, "bad handle {}", handle.0)"#)]
+		BadHandle(Handle),
+	}
+
+	impl ::std::fmt::Debug for ErrorKind {
+		fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+			::std::fmt::Display::fmt(self, f)
+		}
+	}
+
+	fn debug_error(f: &mut ::std::fmt::Formatter, err: &Error) -> ::std::fmt::Result {
+		write!(f, "Error({:?})", err.kind())
+	}
+
+	let err: Error = ErrorKind::BadHandle(Handle(5)).into();
+	assert_eq!(format!("{:?}", err), "Error(bad handle 5)");
+}
+
+fn causes() {
+	#[derive(Debug, ErrorChain)]
+	pub enum ErrorKind {
+		Msg(String),
+	}
+
+	let err = Error::with_chain(::std::io::Error::new(::std::io::ErrorKind::NotFound, "file not found"), "could not read config");
+	let err = err.context("could not start");
+
+	let causes: Vec<_> = err.causes().map(|err| err.to_string()).collect();
+	assert_eq!(causes, ["could not read config", "file not found"]);
+	assert_eq!(err.causes().count() + 1, err.source_chain().count());
+
+	let err: Error = "no causes".into();
+	assert!(err.causes().next().is_none());
+}
+
+fn chain_delimiter() {
+	#[derive(Debug, ErrorChain)]
+	#[error_chain(chain_delimiter = " <- ")]
+	pub enum ErrorKind {
+		Msg(String),
+	}
+
+	let err = Error::with_chain(::std::io::Error::new(::std::io::ErrorKind::NotFound, "file not found"), "could not read config");
+	let err = err.context("could not start");
+	assert_eq!(format!("{:#}", err), "could not start <- could not read config <- file not found");
+	assert_eq!(format!("{}", err), "could not start");
+}
+
+fn arc_foreign_link() {
+	#[derive(Debug)]
+	struct Outer(::std::io::Error);
+
+	impl ::std::fmt::Display for Outer {
+		fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result { f.write_str("outer") }
+	}
+
+	impl ::std::error::Error for Outer {
+		fn source(&self) -> Option<&(::std::error::Error + 'static)> { Some(&self.0) }
+	}
+
+	#[derive(Debug, ErrorChain)]
+	pub enum ErrorKind {
+		Msg(String),
+
+		#[error_chain(foreign)]
+		Io(::std::sync::Arc<::std::io::Error>),
+
+		#[error_chain(foreign)]
+		Outer(::std::sync::Arc<Outer>),
+	}
+
+	let io_err = ::std::sync::Arc::new(::std::io::Error::new(::std::io::ErrorKind::NotFound, "file not found"));
+	let err: Error = io_err.clone().into();
+	assert_eq!(err.to_string(), "file not found");
+	match *err.kind() {
+		ErrorKind::Io(ref err) => assert!(::std::sync::Arc::ptr_eq(err, &io_err)),
+		_ => unreachable!(),
+	}
+
+	let err: Error = ::std::sync::Arc::new(Outer(::std::io::Error::new(::std::io::ErrorKind::NotFound, "file not found"))).into();
+	assert_eq!(err.to_string(), "outer");
+	assert_eq!(::std::error::Error::source(&err).unwrap().to_string(), "file not found");
+}
+
+fn type_alias_link() {
+	mod inner {
+		#[derive(Debug, ErrorChain)]
+		#[error_chain(backtrace_env = "DERIVE_ERROR_CHAIN_TESTS_TYPE_ALIAS_BACKTRACE")]
+		pub enum ErrorKind {
+			Msg(String),
+		}
+	}
+
+	type InnerErr = inner::Error;
+
+	#[derive(Debug, ErrorChain)]
+	pub enum ErrorKind {
+		Msg(String),
+
+		#[error_chain(link = "InnerErr")]
+		Inner(inner::ErrorKind),
+	}
+
+	::std::env::set_var("DERIVE_ERROR_CHAIN_TESTS_TYPE_ALIAS_BACKTRACE", "1");
+	let inner_err: InnerErr = "inner failed".into();
+	assert!(inner_err.backtrace().is_some());
+	let inner_backtrace = inner_err.backtrace().unwrap() as *const _;
+	let err: Error = inner_err.into();
+	match *err.kind() {
+		ErrorKind::Inner(inner::ErrorKind::Msg(ref s)) if s == "inner failed" => (),
+		_ => unreachable!(),
+	}
+	assert_eq!(err.backtrace().unwrap() as *const _, inner_backtrace);
+
+	// Chaining the inner error reuses its backtrace, found by downcasting the chained error to the alias
+	let inner_err: InnerErr = "inner failed".into();
+	::std::env::remove_var("DERIVE_ERROR_CHAIN_TESTS_TYPE_ALIAS_BACKTRACE");
+	assert!(inner_err.backtrace().is_some());
+	let inner_backtrace = inner_err.backtrace().unwrap() as *const _;
+	let err = Error::with_chain(inner_err, "outer failed");
+	assert_eq!(err.backtrace().unwrap() as *const _, inner_backtrace);
+}
+
+fn result_ok_default() {
+	#[derive(Debug, ErrorChain)]
+	#[error_chain(result_ok_default)]
+	pub enum ErrorKind {
+		Msg(String),
+	}
+
+	fn succeed() -> Result {
+		Ok(())
+	}
+
+	fn fail() -> Result {
+		Err("failed".into())
+	}
+
+	fn value() -> Result<u32> {
+		Ok(5)
+	}
+
+	succeed().unwrap();
+	assert_eq!(fail().unwrap_err().to_string(), "failed");
+	assert_eq!(value().unwrap(), 5);
+
+	mod with_result_default {
+		#[derive(Debug, ErrorChain)]
+		#[error_chain(result_ok_default, result_default)]
+		pub enum ErrorKind {
+			Msg(String),
+		}
 
-	#[cfg_attr(feature = "cargo-clippy", allow(fallible_impl_from))]
-	impl From<String> for ErrorKind {
-		fn from(_: String) -> Self {
-			unimplemented!()
+		pub fn succeed() -> Result {
+			Ok(())
+		}
+
+		pub fn io() -> Result<(), ::std::io::Error> {
+			Ok(())
 		}
 	}
+
+	with_result_default::succeed().unwrap();
+	with_result_default::io().unwrap();
 }
 
-fn macro_conflicts() {
+fn kind_mut() {
 	#[derive(Debug, ErrorChain)]
-	#[error_chain(result = "MyResult")]
+	#[error_chain(backtrace_env = "DERIVE_ERROR_CHAIN_TESTS_KIND_MUT_BACKTRACE")]
 	pub enum ErrorKind {
 		Msg(String),
+	}
+
+	::std::env::set_var("DERIVE_ERROR_CHAIN_TESTS_KIND_MUT_BACKTRACE", "1");
+	let mut err = Error::with_chain(::std::io::Error::new(::std::io::ErrorKind::NotFound, "file not found"), "password is hunter2");
+	::std::env::remove_var("DERIVE_ERROR_CHAIN_TESTS_KIND_MUT_BACKTRACE");
+	assert!(err.backtrace().is_some());
+	let backtrace = err.backtrace().unwrap() as *const _;
+
+	match *err.kind_mut() {
+		ErrorKind::Msg(ref mut s) => *s = s.replace("hunter2", "<redacted>"),
+	}
+
+	assert_eq!(err.to_string(), "password is <redacted>");
+	assert_eq!(::std::error::Error::source(&err).unwrap().to_string(), "file not found");
+	assert_eq!(err.backtrace().unwrap() as *const _, backtrace);
+}
+
+fn force_from() {
+	#[derive(Debug, ErrorChain)]
+	pub enum ErrorKind<T: ::std::error::Error + Send + 'static> {
+		#[error_chain(foreign, force_from)]
+		Foreign(T),
 
 		#[error_chain(custom)]
 		Code(i32),
 	}
 
-	error_chain! {
-		types { ECError, ECErrorKind, ECResultExt, ECResult; }
+	fn parse(s: &str) -> Result<::std::num::ParseIntError, i32> {
+		Ok(s.parse()?)
 	}
 
-	quick_main!(|| -> MyResult<()> {
-		bail!("failed")
-	});
+	assert_eq!(parse("5").unwrap(), 5);
+	match *parse("five").unwrap_err().kind() {
+		ErrorKind::Foreign(ref err) => assert_eq!(err.to_string(), "invalid digit found in string"),
+		_ => unreachable!(),
+	}
+}
 
-	fn foo() -> MyResult<()> {
-		bail!("failed")
+fn extract() {
+	use std::convert::TryFrom;
+
+	#[derive(Debug, ErrorChain)]
+	pub enum ErrorKind {
+		Msg(String),
+
+		#[error_chain(custom, extract)]
+		HttpStatus(u32),
+
+		#[error_chain(custom, extract)]
+		Request { url: String, status: u16 },
+
+		#[error_chain(foreign, extract)]
+		Io(::std::io::Error),
 	}
 
-	match foo() {
-		Ok(_) => unreachable!(),
-		Err(err) => match *err.kind() {
-			ErrorKind::Msg(ref s) if s == "failed" => (),
-			_ => unreachable!(),
-		},
+	let err: Error = ErrorKind::HttpStatus(404).into();
+	assert_eq!(u32::try_from(err).unwrap(), 404);
+
+	let err: Error = "not a status".into();
+	let err = u32::try_from(err).unwrap_err();
+	assert_eq!(err.to_string(), "not a status");
+
+	let err: Error = (ErrorKind::Request { url: "http://example.com".to_string(), status: 500 }).into();
+	assert_eq!(<(String, u16)>::try_from(err).unwrap(), ("http://example.com".to_string(), 500));
+
+	let err: Error = ::std::io::Error::new(::std::io::ErrorKind::NotFound, "file not found").into();
+	assert_eq!(::std::io::Error::try_from(err).unwrap().kind(), ::std::io::ErrorKind::NotFound);
+}
+
+fn from_unit() {
+	#[derive(Debug, ErrorChain)]
+	#[error_chain(from_unit = "Unknown")]
+	pub enum ErrorKind {
+		Msg(String),
+
+		#[error_chain(custom, display = r#"|| write!(f, "unknown error")"#)]
+		Unknown,
+	}
+
+	fn lookup(key: &str) -> ::std::result::Result<u32, ()> {
+		if key == "answer" { Ok(42) } else { Err(()) }
+	}
+
+	fn get(key: &str) -> Result<u32> {
+		let value = lookup(key)?;
+		Ok(value)
+	}
+
+	assert_eq!(get("answer").unwrap(), 42);
+	let err = get("question").unwrap_err();
+	match *err.kind() {
+		ErrorKind::Unknown => (),
+		_ => unreachable!(),
 	}
+	assert_eq!(err.to_string(), "unknown error");
+
+	let kind: ErrorKind = ().into();
+	assert_eq!(kind.kind_name(), "Unknown");
 }
 
-mod need_not_be_pub {
+#[deny(deprecated)]
+mod deprecated_test {
+	mod inner {
+		#[derive(Debug, ErrorChain)]
+		pub enum ErrorKind {
+			Msg(String),
+		}
+	}
+
 	#[derive(Debug, ErrorChain)]
-	enum ErrorKind {
+	#[error_chain(predicates)]
+	pub enum ErrorKind {
+		Msg(String),
+
+		/// An error that is no longer returned.
+		#[deprecated(note = "use `Code` instead")]
+		#[error_chain(custom, description = r#"|_| "old code""#, display = r#"|code| write!(f, "old code {}", code)"#)]
+		OldCode(i32),
+
+		#[deprecated]
+		#[error_chain(link = "inner::Error")]
+		Inner(inner::ErrorKind),
+
+		#[deprecated]
+		#[error_chain(foreign)]
+		Io(::std::io::Error),
+
+		#[error_chain(custom)]
+		Code(i32),
+	}
+
+	pub fn test() {
+		#[allow(deprecated)]
+		let err: Error = ErrorKind::OldCode(5).into();
+		assert_eq!(err.to_string(), "old code 5");
+		assert!(err.is_old_code());
+
+		let err: Error = ::std::io::Error::new(::std::io::ErrorKind::NotFound, "file not found").into();
+		assert!(err.is_io());
+	}
+}
+
+mod visibility_test {
+	pub mod errors {
+		pub mod crate_errors {
+			#[derive(Debug, ErrorChain)]
+			#[error_chain(prelude = "prelude")]
+			pub(crate) enum ErrorKind {
+				Msg(String),
+			}
+		}
+
+		pub mod inner {
+			#[derive(Debug, ErrorChain)]
+			#[error_chain(prelude = "prelude")]
+			pub(in visibility_test::errors) enum ErrorKind {
+				Msg(String),
+
+				#[error_chain(custom)]
+				Code(i32),
+			}
+		}
+
+		pub(in visibility_test::errors) fn restricted() -> inner::Result<()> {
+			use self::inner::prelude::*;
+
+			let result: Result<()> = Err(ErrorKind::Code(5).into());
+			result.chain_err(|| "restricted")
+		}
+
+		pub fn test() {
+			let err = restricted().unwrap_err();
+			assert_eq!(err.to_string(), "restricted");
+			assert_eq!(err.iter().nth(1).unwrap().to_string(), "Code");
+		}
+	}
+
+	pub fn test() {
+		use self::errors::crate_errors::prelude::*;
+
+		let result: Result<()> = Err("crate".into());
+		let err: Error = result.chain_err(|| ErrorKind::Msg("outer".to_string())).unwrap_err();
+		assert_eq!(err.to_string(), "outer");
+
+		errors::test();
+	}
+}
+
+fn fallback_display() {
+	#[derive(Debug, ErrorChain)]
+	#[error_chain(display = "display_kind")]
+	pub enum ErrorKind {
+		Msg(String),
+
+		#[error_chain(foreign)]
+		Io(::std::io::Error),
+
+		#[error_chain(custom)]
+		HttpStatus(u32),
+
+		#[error_chain(custom)]
+		#[error_chain(display = r#"|t| write!(f, "invalid token {}", t)"#)]
+		InvalidToken(String),
+	}
+
+	fn display_kind(f: &mut ::std::fmt::Formatter, kind: &ErrorKind) -> ::std::fmt::Result {
+		write!(f, "{} failed", kind.kind_name())
+	}
+
+	assert_eq!(ErrorKind::Msg("Test".to_string()).to_string(), "Test");
+	assert_eq!(ErrorKind::Io(::std::io::Error::new(::std::io::ErrorKind::NotFound, "file not found")).to_string(), "Io failed");
+	assert_eq!(ErrorKind::HttpStatus(404).to_string(), "HttpStatus failed");
+	assert_eq!(ErrorKind::InvalidToken("foo".to_string()).to_string(), "invalid token foo");
+}
+
+fn must_use() {
+	#[derive(Debug, ErrorChain)]
+	#[error_chain(must_use)]
+	pub enum ErrorKind {
+		Msg(String),
+	}
+
+	let err = Error::from("Test");
+	assert_eq!(err.to_string(), "Test");
+}
+
+fn empty_attributes_and_trailing_commas() {
+	#[derive(Debug, ErrorChain)]
+	#[error_chain()]
+	#[error_chain(result = "MyResult",)]
+	pub enum ErrorKind {
+		Msg(String),
+
+		#[error_chain()]
+		#[error_chain(custom,)]
+		HttpStatus(u32),
+
+		#[error_chain(foreign,)]
+		#[error_chain(display = r#"|e| write!(f, "I/O error: {}", e)"#,)]
+		Io(::std::io::Error),
+	}
+
+	let _: MyResult<()> = Ok(());
+	assert_eq!(ErrorKind::HttpStatus(5).to_string(), "HttpStatus");
+	assert_eq!(ErrorKind::Io(::std::io::Error::new(::std::io::ErrorKind::NotFound, "file not found")).to_string(), "I/O error: file not found");
+}
+
+fn ord() {
+	#[derive(Debug, PartialEq, Eq, ErrorChain)]
+	#[error_chain(ord)]
+	pub enum ErrorKind {
+		Msg(String),
+
+		#[error_chain(custom)]
+		HttpStatus(u32),
+
+		#[error_chain(custom)]
+		InvalidToken { line: u32, column: u32 },
+
+		#[error_chain(custom)]
+		Unknown,
+	}
+
+	let mut kinds = vec![
+		ErrorKind::Unknown,
+		ErrorKind::InvalidToken { line: 2, column: 1 },
+		ErrorKind::HttpStatus(500),
+		ErrorKind::InvalidToken { line: 1, column: 5 },
+		ErrorKind::Msg("b".to_string()),
+		ErrorKind::HttpStatus(404),
+		ErrorKind::InvalidToken { line: 1, column: 2 },
+		ErrorKind::Msg("a".to_string()),
+	];
+	kinds.sort();
+
+	assert_eq!(kinds, vec![
+		ErrorKind::Msg("a".to_string()),
+		ErrorKind::Msg("b".to_string()),
+		ErrorKind::HttpStatus(404),
+		ErrorKind::HttpStatus(500),
+		ErrorKind::InvalidToken { line: 1, column: 2 },
+		ErrorKind::InvalidToken { line: 1, column: 5 },
+		ErrorKind::InvalidToken { line: 2, column: 1 },
+		ErrorKind::Unknown,
+	]);
+
+	let set: ::std::collections::BTreeSet<_> = vec![ErrorKind::Unknown, ErrorKind::HttpStatus(404), ErrorKind::Unknown].into_iter().collect();
+	assert_eq!(set.len(), 2);
+}
+
+fn chain_err_kind() {
+	#[derive(Debug, ErrorChain)]
+	pub enum ErrorKind {
+		Msg(String),
+
+		#[error_chain(custom)]
+		#[error_chain(display = r#"|code| write!(f, "error code {}", code)"#)]
+		Code(u32),
+	}
+
+	let err_result: ::std::result::Result<(), _> = Err(::std::io::Error::new(::std::io::ErrorKind::NotFound, "file not found"));
+	let err = err_result.chain_err_kind(ErrorKind::Code(5)).unwrap_err();
+	match *err.kind() {
+		ErrorKind::Code(5) => { },
+		_ => panic!(),
+	}
+	assert_eq!(err.to_string(), "error code 5");
+	assert_eq!(::std::error::Error::source(&err).unwrap().to_string(), "file not found");
+
+	let err = None::<()>.chain_err_kind("Not found").unwrap_err();
+	assert_eq!(err.to_string(), "Not found");
+	assert!(::std::error::Error::source(&err).is_none());
+
+	assert_eq!(Some(5).chain_err_kind(ErrorKind::Code(5)).unwrap(), 5);
+}
+
+fn ok_or_kind() {
+	#[derive(Debug, ErrorChain)]
+	pub enum ErrorKind {
+		Msg(String),
+
+		#[error_chain(custom)]
+		Test,
+	}
+
+	let err = None::<i32>.ok_or_kind(ErrorKind::Test).unwrap_err();
+	match *err.kind() {
+		ErrorKind::Test => { },
+		_ => panic!(),
+	}
+	assert!(::std::error::Error::source(&err).is_none());
+
+	assert_eq!(Some(5).ok_or_kind(ErrorKind::Test).unwrap(), 5);
+
+	let err = None::<i32>.ok_or_kind("Not found").unwrap_err();
+	assert_eq!(err.to_string(), "Not found");
+}
+
+fn foreign_context() {
+	#[derive(Debug)]
+	pub struct Wrapper(::std::io::Error);
+
+	impl ::std::fmt::Display for Wrapper {
+		fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result { write!(f, "wrapper") }
+	}
+
+	impl ::std::error::Error for Wrapper {
+		fn source(&self) -> Option<&(::std::error::Error + 'static)> { Some(&self.0) }
+	}
+
+	#[derive(Debug, ErrorChain)]
+	pub enum ErrorKind {
+		Msg(String),
+
+		#[error_chain(foreign, context = "reading config: {0}")]
+		Io(::std::io::Error),
+
+		#[error_chain(foreign, context = "parsing config: {source}")]
+		Parse { source: ::std::num::ParseIntError },
+
+		#[error_chain(foreign, context = "loading config: {0}")]
+		Wrapped(Wrapper),
+	}
+
+	let err: Error = ::std::io::Error::new(::std::io::ErrorKind::NotFound, "file not found").into();
+	assert_eq!(err.to_string(), "reading config: file not found");
+	assert!(::std::error::Error::cause(&err).is_none());
+
+	let err: Error = "a".parse::<u32>().unwrap_err().into();
+	assert_eq!(err.to_string(), "parsing config: invalid digit found in string");
+
+	// The context only changes the message. The source of the foreign error is still forwarded.
+	let err: Error = Wrapper(::std::io::Error::new(::std::io::ErrorKind::NotFound, "file not found")).into();
+	assert_eq!(err.to_string(), "loading config: wrapper");
+	assert_eq!(::std::error::Error::source(&err).unwrap().to_string(), "file not found");
+	assert_eq!(::std::error::Error::cause(&err).unwrap().to_string(), "file not found");
+}
+
+fn dynamic_description() {
+	mod inner {
+		#[derive(Debug, ErrorChain)]
+		#[error_chain(dynamic_description)]
+		pub enum ErrorKind {
+			Msg(String),
+
+			#[error_chain(custom, description = r#"|code| format!("HTTP status {}", code)"#)]
+			HttpStatus(u32),
+		}
+	}
+
+	#[derive(Debug, ErrorChain)]
+	#[error_chain(dynamic_description)]
+	pub enum ErrorKind {
+		Msg(String),
+
+		#[error_chain(link = "inner::Error")]
+		Inner(inner::ErrorKind),
+
+		#[error_chain(foreign)]
+		Io(::std::io::Error),
+
+		#[error_chain(custom, description = "token_description")]
+		InvalidToken { line: u32 },
+
+		#[error_chain(custom, description = r#"|| "unknown""#)]
+		Unknown,
+
+		#[error_chain(custom)]
+		Other,
+	}
+
+	fn token_description(line: &u32) -> String {
+		format!("invalid token on line {}", line)
+	}
+
+	assert_eq!(ErrorKind::Msg("Test".to_string()).description(), "Test");
+	assert_eq!(ErrorKind::Inner(inner::ErrorKind::HttpStatus(404)).description(), "HTTP status 404");
+	assert_eq!(ErrorKind::InvalidToken { line: 5 }.description(), "invalid token on line 5");
+	assert_eq!(ErrorKind::Unknown.description(), "unknown");
+	assert_eq!(ErrorKind::Other.description(), "Other");
+	assert_eq!(ErrorKind::Other.to_string(), "Other");
+}
+
+fn into_boxed() {
+	#[derive(Debug, ErrorChain)]
+	#[error_chain(into_boxed)]
+	pub enum ErrorKind {
+		Msg(String),
+	}
+
+	fn takes_boxed(err: Box<::std::error::Error + Send + Sync>) -> String {
+		err.to_string()
+	}
+
+	assert_eq!(takes_boxed(ErrorKind::Msg("x".into()).into()), "x");
+
+	let err = Error::with_chain(::std::io::Error::new(::std::io::ErrorKind::NotFound, "file not found"), "Could not read config");
+	let snapshot = err.sync_snapshot();
+	assert_eq!(snapshot.to_string(), "Could not read config");
+	assert!(snapshot.downcast_ref::<Error>().is_none());
+	assert_eq!(snapshot.source().unwrap().to_string(), "file not found");
+	assert!(snapshot.source().unwrap().source().is_none());
+}
+
+fn generic_default() {
+	#[derive(Debug, ErrorChain)]
+	#[error_chain(result_default)]
+	pub enum ErrorKind<T: ::std::fmt::Debug + Send + 'static = String> {
 		Msg(String),
+
+		#[error_chain(custom)]
+		Value(T),
+	}
+
+	fn fails() -> Result<()> {
+		Err(ErrorKind::Value("bad value".to_string()).into())
+	}
+
+	fn fails_with_code() -> Result<(), Error<i32>> {
+		Err(ErrorKind::Value(5).into())
+	}
+
+	let err: Error = fails().unwrap_err();
+	match *err.kind() {
+		ErrorKind::Value(ref s) if s == "bad value" => (),
+		_ => unreachable!(),
+	}
+
+	match *fails_with_code().unwrap_err().kind() {
+		ErrorKind::Value(5) => (),
+		_ => unreachable!(),
+	}
+
+	mod without_result_default {
+		#[derive(Debug, ErrorChain)]
+		pub enum ErrorKind<T: ::std::fmt::Debug + Send + 'static = String> {
+			Msg(String),
+
+			#[error_chain(custom)]
+			Value(T),
+		}
+
+		pub fn fails() -> Result<()> {
+			Err(ErrorKind::Value("bad value".to_string()).into())
+		}
+
+		pub fn fails_with_code() -> Result<(), i32> {
+			Err(ErrorKind::Value(5).into())
+		}
+	}
+
+	let err: without_result_default::Error = without_result_default::fails().unwrap_err();
+	match *err.kind() {
+		without_result_default::ErrorKind::Value(ref s) if s == "bad value" => (),
+		_ => unreachable!(),
+	}
+
+	match *without_result_default::fails_with_code().unwrap_err().kind() {
+		without_result_default::ErrorKind::Value(5) => (),
+		_ => unreachable!(),
 	}
 }
@@ -87,11 +87,40 @@
 //! The less obvious differences are:
 //!
 //! - The `ErrorKind` must explicitly implement `::std::fmt::Debug`, either automatically using `#[derive]` or manually implemented separately. `error_chain!` does this implicitly.
+//!   Other traits like `Clone`, `PartialEq`, `Eq` and `Hash` can be derived for the `ErrorKind` the same way, alongside `ErrorChain`.
+//!   Since the `ErrorKind` is still defined by the original enum, there is no attribute to re-emit it with other derives.
+//!   For the same reason, the enum and its variants keep all their other attributes, like doc comments, `#[cfg]` and `#[deprecated]`.
+//!   The generated code does not cause deprecation warnings for `#[deprecated]` variants, but other uses of them still do.
 //! - Unlike `error_chain!`, the `ErrorKind` need not have `pub` visibility. The generated `Error`, `Result` and `ResultExt` will have the same visibility as the `ErrorKind`.
+//!   This includes restricted visibilities like `pub(crate)` and `pub(in some::path)`.
 //! - The `ErrorKind` can have a special `Msg(String)` member for converting strings to the `ErrorKind`. `error_chain!` does this implicitly.
-//! - Unlike `error-chain`, the `Msg(String)` member is optional. If absent, the `ErrorKind` and `Error` will not impl `From<String>` and `From<&str>`.
+//!   The member can also be declared as `Msg(Box<str>)` to keep the `ErrorKind` smaller.
+//!   Besides strings, `::std::fmt::Arguments` can be converted too, such as `Error::from(format_args!("invalid value {}", value))`.
+//! - Unlike `error-chain`, the `Msg(String)` member is optional. If absent, the `ErrorKind` and `Error` will not impl `From<String>`, `From<&str>` and `From<::std::fmt::Arguments>`,
+//!   and the `Error` will not have the `context()` method that chains it under a new `Msg` made from any `::std::fmt::Display` value.
+//! - The `ErrorKind` can have no variants at all, such as for a placeholder error type early in development. The generated `Error` cannot be constructed then,
+//!   but it can still be used as the error type of functions that never fail.
+//! - The generated `Error` has a `new` constructor as an alias of `from_kind`, so `Error::new(kind)` works too.
+//! - The constructors of the generated `Error`, `from_kind`, `new`, `with_chain` and `with_boxed_chain`, are marked `#[cold]`, and the last two
+//!   also `#[inline(never)]`, since constructing an error is the unlikely path. So are the closures that `ResultExt` uses to construct errors.
+//!   In a tight loop that rarely fails, the optimizer then keeps the code that constructs the error and captures the backtrace out of line,
+//!   and lays out the loop for the success path. Their behavior is otherwise the same.
+//! - If the generated `Error` doesn't have a backtrace of its own, `Error::backtrace()` returns the backtrace of the first error in its chain
+//!   that has one and is of the same type or the type of one of its chainable links. This can happen when an error that captured a backtrace
+//!   is chained through another error type. This fallback is only done when the `ErrorKind` has no lifetime or type parameters,
+//!   so that `Error::backtrace()` never requires the `Error` to be `'static`.
+//! - Formatting the generated `Error` with `{:#}` prints the whole chain, with each error in it after a `: caused by: ` separator.
+//!   Each error is formatted with `{}`, so one that prints its own chain for `{:#}` doesn't repeat it.
+//!   The separator can be changed with the `chain_delimiter` attribute.
+//!   Formatting it with `{}` only prints the error itself.
+//! - Variants can have explicit discriminants, such as `FileNotFound = 404` on a unit custom link. The `ErrorKind` then has a `code()` method
+//!   that returns the discriminant of the variant as an `Option<i64>`, or `None` for variants without one.
 //! - Doc comments, since they're effectively attributes, can be applied on the enum variants without any special syntax like `error_chain!` has.
-//! - The `ErrorKind` can be generic.
+//! - The `ErrorKind` can be generic. It can also have lifetime parameters, though the `ResultExt` impls and the methods that chain errors
+//!   are only available when they're `'static`, since `error-chain` requires chained errors to be `'static`.
+//!   Type parameters can have defaults, which are carried over to the generated `Error` and the `Result` alias.
+//! - The attributes must be written as `#[error_chain(...)]`. Qualified paths like `#[derive_error_chain::error_chain(...)]` are not supported,
+//!   and fail with an error that says so, instead of being left on the variant to fail later as an unknown attribute.
 //!
 //! # Enum attributes
 //!
@@ -102,595 +131,2330 @@
 //! - `#[error_chain(result_ext = "ResultExtName")]`
 //!
 //!     Override the name of the generated `ResultExt` trait to the given name. If not provided, the trait will be named `ResultExt`.
+//!     If set to the empty string `""`, the trait will not be generated at all.
 //!
 //! - `#[error_chain(result = "ResultName")]`
 //!
 //!     Override the name of the generated `Result` type alias to the given name. If not provided, the alias will be named `Result`.
 //!     If set to the empty string `""`, the alias will not be generated at all.
 //!
-//! - `#[error_chain(backtrace = "false")]` or `#[error_chain(backtrace = false)]`
+//! - `#[error_chain(result_error = "Error<::std::io::Error>")]`
 //!
-//!     Disable backtrace functionality in the generated code. This should be kept in sync with the value of the `backtrace` feature of the `error-chain` crate.
-//!     In other words, if you set `backtrace = "false"` here, you must also specify `default-features = false` for `error-chain` in your `Cargo.toml`
+//!     Use the given type as the error type of the generated `Result` type alias instead of the generated `Error` with the `ErrorKind`'s own parameters.
+//!     This is useful for a generic `ErrorKind` to expose an alias for a particular instantiation, ie `IoResult<T>` for `Result<T, Error<::std::io::Error>>`
+//!     with `#[error_chain(result = "IoResult", result_error = "Error<::std::io::Error>")]`. The alias then only has the `T` parameter,
+//!     so the type must not refer to the `ErrorKind`'s parameters.
 //!
-//! # Variant definitions
+//! - `#[error_chain(result_default)]`
 //!
-//! - Chainable links
+//!     Generate the `Result` type alias with a second type parameter for the error type that defaults to the generated `Error`,
+//!     ie `Result<T, E = Error>` instead of `Result<T>`. This allows the alias to also be used with other error types, such as in generic code.
+//!
+//! - `#[error_chain(result_ok_default)]`
+//!
+//!     Give the `T` parameter of the `Result` type alias a default of `()`, ie `Result<T = ()>`, so that a bare `Result` can be used
+//!     as the return type of functions that don't return a value. This can be combined with `result_default` and `result_error`.
 //!
 //!     ```
 //!     # #[macro_use] extern crate derive_error_chain;
 //!     #
-//!     # mod other_error {
-//!     #     #[derive(Debug, ErrorChain)]
-//!     #     pub enum ErrorKind {
-//!     #         Msg(String),
-//!     #     }
-//!     # }
+//!     #[derive(Debug, ErrorChain)]
+//!     #[error_chain(result_ok_default)]
+//!     pub enum ErrorKind {
+//!         Msg(String),
+//!     }
+//!
+//!     fn run() -> Result {
+//!         Ok(())
+//!     }
 //!     #
-//!     # #[derive(Debug, ErrorChain)]
-//!     # pub enum ErrorKind {
-//!     #[error_chain(link = "other_error::Error")]
-//!     Another(other_error::ErrorKind),
+//!     # fn main() {
+//!     #     run().unwrap();
 //!     # }
 //!     ```
 //!
-//!     A chainable link is an error and errorkind that have been generated using `error-chain` or `derive-error-chain`. The variant must have a single field
-//!     to hold the chained errorkind, and the `link` attribute must specify a path to the chained error.
+//! - `#[error_chain(error_partial_eq)]`
 //!
-//!     When the `use_extern_macros` feature is enabled, the value of the `link` attribute does not need to be stringified:
+//!     Implement `PartialEq` for the generated `Error` by comparing the `ErrorKind`s, which must themselves implement `PartialEq`.
+//!     The backtraces and error chains are not compared, so two errors with different causes but equal kinds compare equal.
+//!
+//! - `#[error_chain(error_eq)]`, `#[error_chain(error_hash)]`
+//!
+//!     Implement `Eq` and `Hash` respectively for the generated `Error`, using only the `ErrorKind`, which must itself implement `Eq` or `Hash`.
+//!     Like with `error_partial_eq`, the backtraces and error chains are neither compared nor hashed. This allows errors to be deduplicated
+//!     in a `HashSet`, for example. `error_eq` requires `error_partial_eq`.
 //!
 //!     ```
-//!     # #![feature(use_extern_macros)]
-//!     #
 //!     # #[macro_use] extern crate derive_error_chain;
 //!     #
-//!     # mod other_error {
-//!     #     #[derive(Debug, ErrorChain)]
-//!     #     pub enum ErrorKind {
-//!     #         Msg(String),
-//!     #     }
-//!     # }
+//!     #[derive(Debug, PartialEq, Eq, Hash, ErrorChain)]
+//!     #[error_chain(error_partial_eq, error_eq, error_hash)]
+//!     pub enum ErrorKind {
+//!         Msg(String),
+//!     }
 //!     #
-//!     # #[derive(Debug, ErrorChain)]
-//!     # pub enum ErrorKind {
-//!     #[error_chain(link = other_error::Error)]
-//!     Another(other_error::ErrorKind),
+//!     # fn main() {
+//!     let errors: ::std::collections::HashSet<Error> = vec![Error::from("oops"), Error::from("oops")].into_iter().collect();
+//!     assert_eq!(errors.len(), 1);
 //!     # }
 //!     ```
 //!
-//! - Foreign links
+//! - `#[error_chain(error_doc = "...")]`, `#[error_chain(result_doc = "...")]`, `#[error_chain(result_ext_doc = "...")]`
+//!
+//!     Override the doc comments of the generated `Error` struct, `Result` type alias and `ResultExt` trait respectively with the given strings.
+//!     If not provided, the items will have generic doc comments.
+//!
+//! - `#[error_chain(prelude = "prelude_name")]`
+//!
+//!     Generate a module with the given name that re-exports the generated `Error`, `Result` and `ResultExt` as well as the `ErrorKind`,
+//!     so that they can all be imported with `use errors::prelude_name::*;`. If not provided, the module will not be generated.
+//!
+//! - `#[error_chain(module = "module_name")]`
+//!
+//!     Generate all the items in a module with the given name instead of next to the `ErrorKind`, so that they don't clash with other items
+//!     named `Error` or `Result` in the same scope. The module also re-exports the `ErrorKind`, so the types can be referred to as
+//!     `errors::Error`, `errors::ErrorKind` and so on. The module glob-imports its parent, so any types used by the `ErrorKind` are still
+//!     in scope. This means the `ErrorKind` must be defined directly in a module rather than inside a function.
 //!
 //!     ```
 //!     # #[macro_use] extern crate derive_error_chain;
 //!     #
-//!     # #[derive(Debug, ErrorChain)]
-//!     # pub enum ErrorKind {
-//!     #[error_chain(foreign)]
-//!     Fmt(::std::fmt::Error),
-//!     # }
-//!     ```
+//!     #[derive(Debug, ErrorChain)]
+//!     #[error_chain(module = "errors")]
+//!     pub enum ErrorKind {
+//!         Msg(String),
 //!
-//!     A foreign link is an error that implements `::std::error::Error` but otherwise does not follow `error-chain`'s conventions. The variant must have
-//!     a single field to hold the foreign error.
+//!         #[error_chain(foreign)]
+//!         Io(::std::io::Error),
+//!     }
 //!
-//! - Custom links
+//!     fn read() -> errors::Result<()> {
+//!         use errors::ResultExt;
 //!
-//!     ```
-//!     # #[macro_use] extern crate derive_error_chain;
+//!         Err(::std::io::Error::new(::std::io::ErrorKind::NotFound, "file not found")).chain_err(|| "could not read")
+//!     }
 //!     #
-//!     # #[derive(Debug, ErrorChain)]
-//!     # pub enum ErrorKind {
-//!     #[error_chain(custom)]
-//!     InvalidToolchainName(String),
+//!     # fn main() {
+//!     #     assert_eq!(read().unwrap_err().to_string(), "could not read");
 //!     # }
 //!     ```
 //!
-//!     A custom link is an arbitrary variant that can hold any members.
+//! - `#[error_chain(location)]`
 //!
-//! # Variant attributes
+//!     Record the source location where each `Error` is constructed or chained, such as the call to `ResultExt::chain_err` or `Error::from_kind`,
+//!     or the `?` that converted another error into it. The location is stored in a third field of the `Error` and is returned by `Error::location()`.
+//!     This uses `#[track_caller]`, so it requires Rust 1.46 or later.
 //!
-//! In addition to the above attributes that identify the type of the variant's link, the below attributes can be used on all links.
+//! - `#[error_chain(from_boxed)]`
 //!
-//! - `#[error_chain(description = "some_function_expression")]`
+//!     Implement `From<Box<::std::error::Error + Send + Sync>>` for the generated `Error`. The boxed error is converted into a `Msg` with the same message,
+//!     and is kept as the next error in the chain. This requires the `ErrorKind` to have a `Msg(String)` variant.
 //!
-//!     Specifies a function expression to be used to implement `ErrorKind::description()`.
-//!     This value is also returned from the implementation of `::std::error::Error::description()` on the generated `Error`.
+//! - `#[error_chain(into_boxed)]`
 //!
-//!     This can be an inline lambda:
+//!     Implement `From<ErrorKind>` for `Box<::std::error::Error + Send + Sync>`. The generated `Error` is not `Sync`, so the boxed error
+//!     is a snapshot of the `Error` created from the kind, as returned by `Error::sync_snapshot()`. The snapshot is lossy: every error in the chain
+//!     is replaced by its `::std::fmt::Display` message, so it has the same messages and chain, but the kind, the backtrace and the original errors
+//!     are gone, and it cannot be downcast to the `Error` or to any of the errors in its chain.
+//!
+//!     `Error::sync_snapshot()` is only generated when one of `into_boxed`, `into_io` or `anyhow` is used.
+//!
+//! - `#[error_chain(into_io)]`
+//!
+//!     Implement `From<Error>` for `::std::io::Error`, with `::std::io::ErrorKind::Other`. Like with `into_boxed`, the `::std::io::Error` holds
+//!     the snapshot returned by `Error::sync_snapshot()`, since `::std::io::Error::new()` requires the error to be `Sync`.
+//!
+//! - `#[error_chain(from_unit = "VariantName")]`
+//!
+//!     Implement `From<()>` for the `ErrorKind` and `Error`, converting to the given variant, which must not have any fields.
+//!     This allows `?` to be used on a `Result<T, ()>`, such as when prototyping.
 //!
 //!     ```
 //!     # #[macro_use] extern crate derive_error_chain;
 //!     #
-//!     # #[derive(Debug, ErrorChain)]
-//!     # pub enum ErrorKind {
-//!         # #[error_chain(custom)]
-//!     #[error_chain(description = r#"|_| "invalid toolchain name""#)]
-//!     InvalidToolchainName(String),
+//!     #[derive(Debug, ErrorChain)]
+//!     #[error_chain(from_unit = "Unknown")]
+//!     pub enum ErrorKind {
+//!         Msg(String),
+//!
+//!         #[error_chain(custom)]
+//!         Unknown,
+//!     }
+//!
+//!     fn parse(s: &str) -> Result<u32> {
+//!         let value = s.parse().map_err(|_| ())?;
+//!         Ok(value)
+//!     }
+//!     #
+//!     # fn main() {
+//!     #     assert!(parse("five").is_err());
 //!     # }
 //!     ```
 //!
-//!     or it can be a separate function:
+//! - `#[error_chain(from_str)]`
+//!
+//!     Implement `::std::str::FromStr` for the `ErrorKind`, parsing any string into the `Msg` member. Since this can't fail,
+//!     the `Err` type is `::std::convert::Infallible`. This is useful for tests that construct errors from strings, such as from a config file.
+//!     The `ErrorKind` must have a `Msg` member.
 //!
 //!     ```
 //!     # #[macro_use] extern crate derive_error_chain;
 //!     #
-//!     # #[derive(Debug, ErrorChain)]
-//!     # pub enum ErrorKind {
-//!         # #[error_chain(custom)]
-//!     #[error_chain(description = "invalid_toolchain_name_error_description")]
-//!     InvalidToolchainName(String),
+//!     #[derive(Debug, ErrorChain)]
+//!     #[error_chain(from_str)]
+//!     pub enum ErrorKind {
+//!         Msg(String),
+//!     }
+//!     #
+//!     # fn main() {
+//!     let kind: ErrorKind = "boom".parse().unwrap();
+//!     #     match kind {
+//!     #         ErrorKind::Msg(ref s) if s == "boom" => (),
+//!     #         _ => unreachable!(),
+//!     #     }
 //!     # }
+//!     ```
 //!
-//!     // <snip>
+//! - `#[error_chain(display = "...")]`
 //!
-//!     fn invalid_toolchain_name_error_description(_: &str) -> &str {
-//!         "invalid toolchain name"
-//!     }
-//!     ```
+//!     The display implementation for every variant that doesn't have its own `display` attribute, except `Msg`. Like the `display` variant attribute,
+//!     the value is either a function expression with signature `(f: &mut ::std::fmt::Formatter, kind: &ErrorKind) -> ::std::fmt::Result`,
+//!     or a closure expression with signature `|kind: &ErrorKind| -> ::std::fmt::Result` that uses the captured `f` to write to the formatter.
 //!
-//!     The function expression must have the signature `(...) -> &'static str`. It should have one parameter for each field of the variant.
-//!     The fields are passed in by reference.
+//!     ```
+//!     # #[macro_use] extern crate derive_error_chain;
+//!     #
+//!     #[derive(Debug, ErrorChain)]
+//!     #[error_chain(display = r#"|kind: &ErrorKind| write!(f, "{} error", kind.description())"#)]
+//!     pub enum ErrorKind {
+//!         Msg(String),
 //!
-//!     Thus in the above example, since `InvalidToolchainName` had a single field of type `String`, the function expression needed to be of type
-//!     `(&str) -> &'static str`
+//!         #[error_chain(custom, description = r#"|| "HTTP""#)]
+//!         Http,
 //!
-//!     If not specified, the default implementation behaves in this way:
+//!         #[error_chain(custom, description = r#"|| "TLS""#)]
+//!         Tls,
+//!     }
+//!     #
+//!     # fn main() {
+//!     #     assert_eq!(ErrorKind::Http.to_string(), "HTTP error");
+//!     # }
+//!     ```
 //!
-//!     - Chainable links: Forwards to the chained error kind's `description()`
-//!     - Foreign links: Forwards to the foreign error's implementation of `::std::error::Error::description()`
-//!     - Custom links: Returns the stringified name of the variant.
+//! - `#[error_chain(strict_msg)]`
 //!
-//!     When the `use_extern_macros` feature is enabled, the value does not need to be stringified:
+//!     Require the first member of the `ErrorKind` to be `Msg(String)`, which is where `error_chain!` puts it implicitly. This helps when migrating
+//!     from `error_chain!` with code that relies on the order of the members, such as their discriminants or `Ord` impl.
 //!
-//!     ```
-//!     # #![feature(use_extern_macros)]
-//!     #
+//!     ```compile_fail
 //!     # #[macro_use] extern crate derive_error_chain;
 //!     #
-//!     # #[derive(Debug, ErrorChain)]
-//!     # pub enum ErrorKind {
-//!         # #[error_chain(custom)]
-//!     #[error_chain(description = |_| "invalid toolchain name")]
-//!     InvalidToolchainName(String),
-//!     # }
+//!     #[derive(Debug, ErrorChain)]
+//!     #[error_chain(strict_msg)]
+//!     pub enum ErrorKind {
+//!         #[error_chain(custom)]
+//!         Code(i32),
+//!
+//!         Msg(String),
+//!     }
+//!     #
+//!     # fn main() { }
 //!     ```
 //!
+//! - `#[error_chain(debug_display)]`
+//!
+//!     Display custom variants that don't have their own `display` or `description` attribute like their `Debug` representation, ie with the name
+//!     of the variant followed by its fields formatted with `{:?}`, instead of with just their description. This is handy during development
+//!     when the data of these variants is more useful than their name. A `display` attribute on the `ErrorKind` still takes precedence.
+//!
 //!     ```
-//!     # #![feature(use_extern_macros)]
-//!     #
 //!     # #[macro_use] extern crate derive_error_chain;
 //!     #
-//!     # #[derive(Debug, ErrorChain)]
-//!     # pub enum ErrorKind {
-//!         # #[error_chain(custom)]
-//!     #[error_chain(description = invalid_toolchain_name_error_description)]
-//!     InvalidToolchainName(String),
-//!     # }
+//!     #[derive(Debug, ErrorChain)]
+//!     #[error_chain(debug_display)]
+//!     pub enum ErrorKind {
+//!         Msg(String),
+//!
+//!         #[error_chain(custom)]
+//!         Code(i32),
+//!     }
 //!     #
-//!     # fn invalid_toolchain_name_error_description(_: &str) -> &str {
-//!     #     "invalid toolchain name"
+//!     # fn main() {
+//!     #     assert_eq!(ErrorKind::Code(5).to_string(), "Code(5)");
 //!     # }
 //!     ```
 //!
-//!     When the `use_extern_macros` feature is enabled, closure expressions that only call `write!` on the `::std::fmt::Formatter` can instead use a shorthand:
+//! - `#[error_chain(description = "...")]`
+//!
+//!     The description implementation for every variant that doesn't have its own `description` attribute, except `Msg`. Like the `description`
+//!     variant attribute, the value is either a function expression or a closure expression, but it's called with the whole `&ErrorKind`
+//!     and returns the description as a `&str` (or anything that can be converted to a `Cow<str>` with `dynamic_description`).
 //!
 //!     ```
-//!     # #![feature(use_extern_macros)]
-//!     #
 //!     # #[macro_use] extern crate derive_error_chain;
 //!     #
-//!     # #[derive(Debug, ErrorChain)]
-//!     # pub enum ErrorKind {
-//!         # #[error_chain(custom)]
-//!     #[error_chain(description = const("invalid toolchain name"))]
-//!     InvalidToolchainName(String),
+//!     #[derive(Debug, ErrorChain)]
+//!     #[error_chain(description = r#"|_: &ErrorKind| "network error""#)]
+//!     pub enum ErrorKind {
+//!         Msg(String),
+//!
+//!         #[error_chain(custom)]
+//!         Http,
+//!
+//!         #[error_chain(custom, description = r#"|| "TLS handshake failed""#)]
+//!         Tls,
+//!     }
+//!     #
+//!     # fn main() {
+//!     #     assert_eq!(ErrorKind::Http.description(), "network error");
+//!     #     assert_eq!(ErrorKind::Tls.description(), "TLS handshake failed");
 //!     # }
 //!     ```
 //!
-//! - `#[error_chain(display = "some_function_expression")]`
+//! - `#[error_chain(must_use)]`
 //!
-//!     Specifies a function expression to be used to implement `::std::fmt::Display::fmt()` on the `ErrorKind` and generated `Error`
+//!     Apply `#[must_use]` to the generated `Error` struct, so that constructing an `Error` and then dropping it without handling it causes a warning.
 //!
-//!     This can be an inline lambda:
+//!     ```compile_fail
+//!     #![deny(unused_must_use)]
 //!
-//!     ```
 //!     # #[macro_use] extern crate derive_error_chain;
 //!     #
-//!     # #[derive(Debug, ErrorChain)]
-//!     # pub enum ErrorKind {
-//!         # #[error_chain(custom)]
-//!     #[error_chain(display = r#"|t| write!(f, "invalid toolchain name: '{}'", t)"#)]
-//!     InvalidToolchainName(String),
-//!     # }
+//!     #[derive(Debug, ErrorChain)]
+//!     #[error_chain(must_use)]
+//!     pub enum ErrorKind {
+//!         Msg(String),
+//!     }
+//!
+//!     fn main() {
+//!         Error::from("Oops");
+//!     }
 //!     ```
 //!
-//!     or it can be a separate function:
+//! - `#[error_chain(opaque)]`
 //!
-//!     ```
+//!     Make the fields of the generated `Error` struct private, so that code outside the module can't construct it directly or destructure it,
+//!     and has to use `from_kind()`, `kind()` and the other methods instead. This also keeps `error_chain::State` out of the public API.
+//!     Since a chainable link accesses the fields of the linked `Error`, an opaque `Error` can only be linked from an `ErrorKind`
+//!     in the same module or one of its descendants.
+//!
+//!     ```compile_fail
 //!     # #[macro_use] extern crate derive_error_chain;
 //!     #
-//!     # #[derive(Debug, ErrorChain)]
-//!     # pub enum ErrorKind {
-//!         # #[error_chain(custom)]
-//!     #[error_chain(display = "invalid_toolchain_name_error_display")]
-//!     InvalidToolchainName(String),
-//!     # }
-//!
-//!     // <snip>
+//!     mod errors {
+//!         #[derive(Debug, ErrorChain)]
+//!         #[error_chain(opaque)]
+//!         pub enum ErrorKind {
+//!             Msg(String),
+//!         }
+//!     }
 //!
-//!     fn invalid_toolchain_name_error_display(f: &mut ::std::fmt::Formatter, t: &str) -> ::std::fmt::Result {
-//!         write!(f, "invalid toolchain name: '{}'", t)
+//!     fn main() {
+//!         let errors::Error(kind, _) = "Oops".into();
 //!     }
 //!     ```
 //!
-//!     The function expression must have the signature `(&mut ::std::fmt::Formatter, ...) -> ::std::fmt::Result`.
-//!     It should have one `&mut ::std::fmt::Formatter` parameter, and one parameter for each field of the variant. The fields are passed in by reference.
-//!     For brevity, closure expressions do not need the `&mut ::std::fmt::Formatter` parameter and instead capture `f` from the closure environment.
+//!     The extra fields that other attributes add to the `Error`, such as the one for `location`, are private too.
 //!
-//!     Thus in the above example, since `InvalidToolchainName` had a single field of type `String`, the function expression needed to be of type
-//!     `(&mut ::std::fmt::Formatter, &str) -> ::std::fmt::Result`
+//!     ```compile_fail
+//!     # #[macro_use] extern crate derive_error_chain;
+//!     #
+//!     mod errors {
+//!         #[derive(Debug, ErrorChain)]
+//!         #[error_chain(opaque, location)]
+//!         pub enum ErrorKind {
+//!             Msg(String),
+//!         }
+//!     }
 //!
-//!     If not specified, the default implementation of `::std::fmt::Display::fmt()` behaves in this way:
+//!     fn main() {
+//!         let err: errors::Error = "Oops".into();
+//!         let _ = err.2;
+//!     }
+//!     ```
 //!
-//!     - Chainable links: Forwards to the chained errorkind's implementation of `::std::fmt::Display::fmt()`
-//!     - Foreign links: Forwards to the foreign error's implementation of `::std::fmt::Display::fmt()`
-//!     - Custom links: Writes the description of the variant to the formatter.
+//! - `#[error_chain(error_repr = "C")]`
 //!
-//!     When the `use_extern_macros` feature is enabled, the value does not need to be stringified:
+//!     Apply `#[repr(...)]` with the given value to the generated `Error` struct. `transparent` is not allowed, since the `Error` holds
+//!     the `ErrorKind` and the error chain state, both of which are non-zero-sized. A `#[repr(...)]` on the `ErrorKind` itself
+//!     does not need this attribute; it applies to the `ErrorKind` as usual and does not affect the generated code.
 //!
 //!     ```
-//!     # #![feature(use_extern_macros)]
-//!     #
 //!     # #[macro_use] extern crate derive_error_chain;
 //!     #
-//!     # #[derive(Debug, ErrorChain)]
-//!     # pub enum ErrorKind {
-//!         # #[error_chain(custom)]
-//!     #[error_chain(display = |t| write!(f, "invalid toolchain name: '{}'", t))]
-//!     InvalidToolchainName(String),
+//!     #[derive(Debug, ErrorChain)]
+//!     #[error_chain(error_repr = "C")]
+//!     #[repr(u8)]
+//!     pub enum ErrorKind {
+//!         Msg(String),
+//!     }
+//!     #
+//!     # fn main() {
+//!     #     let _: Error = "Oops".into();
 //!     # }
 //!     ```
 //!
+//! - `#[error_chain(error_debug = "...")]`
+//!
+//!     Implement `::std::fmt::Debug` for the generated `Error` with the given function instead of deriving it, such as when the `ErrorKind`
+//!     has its own `Debug` impl or none at all. Like the `display` attribute, the value is either a function expression with signature
+//!     `(f: &mut ::std::fmt::Formatter, err: &Error) -> ::std::fmt::Result`, or a closure expression with signature
+//!     `|err: &Error| -> ::std::fmt::Result` that uses the captured `f` to write to the formatter.
+//!
 //!     ```
-//!     # #![feature(use_extern_macros)]
-//!     #
 //!     # #[macro_use] extern crate derive_error_chain;
 //!     #
-//!     # #[derive(Debug, ErrorChain)]
-//!     # pub enum ErrorKind {
-//!         # #[error_chain(custom)]
-//!     #[error_chain(display = invalid_toolchain_name_error_display)]
-//!     InvalidToolchainName(String),
-//!     # }
+//!     #[derive(ErrorChain)]
+//!     #[error_chain(error_debug = r#"|err: &Error| write!(f, "Error({})", err)"#)]
+//!     pub enum ErrorKind {
+//!         Msg(String),
+//!     }
 //!     #
-//!     # fn invalid_toolchain_name_error_display(f: &mut ::std::fmt::Formatter, t: &str) -> ::std::fmt::Result {
-//!     #     write!(f, "invalid toolchain name: '{}'", t)
+//!     # fn main() {
+//!     #     let err: Error = "Oops".into();
+//!     #     assert_eq!(format!("{:?}", err), "Error(Oops)");
 //!     # }
 //!     ```
 //!
-//!     When the `use_extern_macros` feature is enabled, closure expressions that only call `write!` on the `::std::fmt::Formatter` can instead use a shorthand:
+//! - `#[error_chain(chain_delimiter = "...")]`
+//!
+//!     The separator written before each error in the chain when the generated `Error` is formatted with `{:#}`. Defaults to `": caused by: "`.
 //!
 //!     ```
-//!     # #![feature(use_extern_macros)]
-//!     #
 //!     # #[macro_use] extern crate derive_error_chain;
 //!     #
-//!     # #[derive(Debug, ErrorChain)]
-//!     # pub enum ErrorKind {
-//!     // Tuple variants use `{0}`, `{1}`, and so on
-//!         # #[error_chain(custom)]
-//!     #[error_chain(display = const("invalid toolchain name: '{0}'"))]
-//!     InvalidToolchainName(String),
+//!     #[derive(Debug, ErrorChain)]
+//!     #[error_chain(chain_delimiter = "\ncaused by: ")]
+//!     pub enum ErrorKind {
+//!         Msg(String),
+//!     }
+//!     #
+//!     # fn main() {
+//!     #     let err = Error::with_chain(Error::from("file not found"), "could not read config");
+//!     #     assert_eq!(format!("{:#}", err), "could not read config\ncaused by: file not found");
 //!     # }
 //!     ```
 //!
+//! - `#[error_chain(ord)]`
+//!
+//!     Implement `PartialOrd` and `Ord` for the `ErrorKind`. Variants are ordered by their order in the enum, and values of the same variant are ordered
+//!     by comparing their fields in order. All the fields must implement `Ord`, and the `ErrorKind` must implement `PartialEq` and `Eq`,
+//!     such as with `#[derive(PartialEq, Eq)]`.
+//!
+//! - `#[error_chain(predicates)]`
+//!
+//!     Generate a predicate method for each variant on the `ErrorKind` and the generated `Error`, named after the variant in snake case,
+//!     such as `is_not_found()` for a `NotFound` variant. The methods on the `Error` check its kind, so `err.is_not_found()` is the same as
+//!     `err.kind().is_not_found()`.
+//!
+//! - `#[error_chain(constructors)]`
+//!
+//!     Generate a constructor for each variant on the generated `Error`, named after the variant in snake case and taking its fields
+//!     as parameters, so that `Error::http_status(404)` is the same as `Error::from_kind(ErrorKind::HttpStatus(404))`. A variant whose name
+//!     would conflict with another method of the `Error`, such as `Kind`, can't be used with this attribute.
+//!
 //!     ```
-//!     # #![feature(use_extern_macros)]
-//!     #
 //!     # #[macro_use] extern crate derive_error_chain;
 //!     #
-//!     # #[derive(Debug, ErrorChain)]
-//!     # pub enum ErrorKind {
-//!     // Struct variants use `{name_of_the_field}`
-//!         # #[error_chain(custom)]
-//!     #[error_chain(display = const("invalid toolchain name: '{name}'"))]
-//!     InvalidToolchainName { name: String },
+//!     #[derive(Debug, ErrorChain)]
+//!     #[error_chain(constructors)]
+//!     pub enum ErrorKind {
+//!         Msg(String),
+//!
+//!         #[error_chain(custom)]
+//!         HttpStatus(u32),
+//!
+//!         #[error_chain(custom)]
+//!         InvalidToken { line: u32, column: u32 },
+//!     }
+//!     #
+//!     # fn main() {
+//!     #     let _ = Error::http_status(404);
+//!     #     let _ = Error::invalid_token(1, 5);
 //!     # }
 //!     ```
 //!
-//! - `#[error_chain(cause = "some_function_expression")]`
+//! - `#[error_chain(dynamic_description)]`
 //!
-//!     Specifies a function expression to be used to implement `::std::fmt::Error::cause()` on the generated `Error`
+//!     Change the return type of the generated `ErrorKind::description()` from `&str` to `::std::borrow::Cow<str>`, so that custom `description`
+//!     functions can return an owned `String` built from the variant's fields, as well as a `&str`. Since `::std::error::Error::description()`
+//!     must return a `&str`, the generated `Error` then uses its deprecated default implementation.
 //!
-//!     This can be an inline lambda:
+//!     A chainable link to an `ErrorKind` with a dynamic description must also have a dynamic description itself, unless it specifies
+//!     its own `description` attribute.
 //!
 //!     ```
 //!     # #[macro_use] extern crate derive_error_chain;
 //!     #
-//!     # #[derive(Debug, ErrorChain)]
-//!     # pub enum ErrorKind {
-//!         # #[error_chain(custom)]
-//!     #[error_chain(cause = "|_, err| err")]
-//!     Io(::std::path::PathBuf, ::std::io::Error),
+//!     #[derive(Debug, ErrorChain)]
+//!     #[error_chain(dynamic_description)]
+//!     pub enum ErrorKind {
+//!         Msg(String),
+//!
+//!         #[error_chain(custom, description = r#"|code| format!("HTTP status {}", code)"#)]
+//!         HttpStatus(u32),
+//!     }
+//!     #
+//!     # fn main() {
+//!     #     assert_eq!(ErrorKind::HttpStatus(404).description(), "HTTP status 404");
 //!     # }
 //!     ```
 //!
-//!     or it can be a separate function:
+//! - `#[error_chain(no_error_description)]`
+//!
+//!     Don't override the deprecated `::std::error::Error::description()` in the impl for the generated `Error`, so that it uses the default one.
+//!     `ErrorKind::description()` is still generated, and the `Display` impls don't depend on the `::std::error::Error` method.
+//!
+//! - `#[error_chain(spantrace)]`
+//!
+//!     Capture a `tracing_error::SpanTrace` whenever an `Error` is constructed or chained, and return it from `Error::spantrace()`.
+//!     The span trace is stored in an extra field of the `Error` after the one for `location`, if any. This requires the `tracing-error` feature
+//!     of this crate, and the crate using it must depend on the `tracing-error` crate and install its `ErrorLayer` for span traces to be captured.
+//!
+//! - `#[error_chain(anyhow)]`
+//!
+//!     Generate an `Error::into_anyhow()` method that converts the `Error` into an `anyhow::Error`, and implement `From<ErrorKind>` for `anyhow::Error`.
+//!     The generated `Error` is not `Sync`, so the `anyhow::Error` is made from the snapshot returned by `Error::sync_snapshot()`.
+//!     It has the same messages and chain, as returned by `anyhow::Error::chain()`, but cannot be downcast to the `Error`.
+//!     `From<Error>` is not implemented for `anyhow::Error` since it would conflict with `anyhow`'s own impl for all `Send + Sync` errors.
+//!     This requires the `anyhow` feature of this crate, and the crate using it must depend on the `anyhow` crate, version 1.0.95 or later.
+//!
+//! - `#[error_chain(no_from)]`
+//!
+//!     Don't implement any of the `From` conversions into the `ErrorKind` and `Error` for the `Msg` variant and the chainable and foreign links,
+//!     such as when two foreign links hold the same error type. The `From<ErrorKind>` impl for the `Error` is still generated,
+//!     so errors can be constructed from their kinds explicitly.
+//!
+//! - `#[error_chain(run)]`
+//!
+//!     Generate a `run()` function that calls the given function returning `Result<()>` and returns an exit code for the process,
+//!     like `error-chain`'s `quick_main!`. If the function fails, the error and its chain are printed to stderr and the exit code is 1,
+//!     otherwise it is 0. The function has the same visibility as the `ErrorKind`, and is not generated if the `Result` alias is not generated.
 //!
 //!     ```
 //!     # #[macro_use] extern crate derive_error_chain;
+//!     # extern crate error_chain;
 //!     #
-//!     # #[derive(Debug, ErrorChain)]
-//!     # pub enum ErrorKind {
-//!         # #[error_chain(custom)]
-//!     #[error_chain(cause = "parse_file_error_cause")]
-//!     Io(::std::path::PathBuf, ::std::io::Error),
-//!     # }
+//!     #[derive(Debug, ErrorChain)]
+//!     #[error_chain(run)]
+//!     pub enum ErrorKind {
+//!         Msg(String),
+//!     }
 //!
-//!     // <snip>
+//!     let exit_code = run(|| {
+//!         // <snip>
+//!         Ok(())
+//!     });
+//!     assert_eq!(exit_code, 0);
+//!     ```
 //!
-//!     fn parse_file_error_cause<'a>(_: &::std::path::Path, err: &'a ::std::io::Error) -> &'a ::std::error::Error {
-//!         err
+//! - `#[error_chain(bail = "bail_macro_name")]`
+//!
+//!     Generate a `macro_rules!` macro with the given name that returns early from the enclosing function with an `Err` made from a `Msg`,
+//!     similar to `error-chain`'s `bail!`. The macro takes a format string and its arguments like `format!`. The `Err` is converted with `Into`
+//!     into the function's error type. The macro refers to the `Error` and `ErrorKind` by name, so they must be in scope where it's used.
+//!     This requires the `ErrorKind` to have a `Msg(String)` variant.
+//!
+//!     ```
+//!     # #[macro_use] extern crate derive_error_chain;
+//!     #
+//!     #[derive(Debug, ErrorChain)]
+//!     #[error_chain(bail = "bail_myerror")]
+//!     pub enum ErrorKind {
+//!         Msg(String),
+//!     }
+//!
+//!     fn check(value: i32) -> Result<()> {
+//!         if value < 0 {
+//!             bail_myerror!("value {} is negative", value);
+//!         }
+//!
+//!         Ok(())
 //!     }
+//!     #
+//!     # fn main() {
+//!     #     assert_eq!(check(-5).unwrap_err().to_string(), "value -5 is negative");
+//!     # }
 //!     ```
 //!
-//!     The function expression must have the signature `(...) -> &::std::error::Error`. It should have one parameter for each field of the variant.
-//!     The fields are passed in by reference. The result is wrapped in `Option::Some()` for returning from `::std::error::Error::cause()`
+//! - `#[error_chain(chain_err = "method_name")]`
 //!
-//!     Thus in the above example, since `Io` had two fields of type `::std::path::PathBuf` and `::std::io::Error`, the function expression needed to be of type
-//!     `(&::std::path::Path, &::std::io::Error) -> &::std::error::Error`
+//!     Override the name of the `chain_err` method of the generated `ResultExt` trait and `Error`, such as to avoid a conflict with another trait
+//!     that has a `chain_err` method. If not provided, the method will be named `chain_err`. The `chain_err` method of the
+//!     `error_chain::ChainedError` impl is not renamed.
 //!
-//!     If not specified, the default implementation of `::std::error::Error::cause()` behaves in this way:
+//! - `#[error_chain(backtrace = "false")]` or `#[error_chain(backtrace = false)]` or `#[error_chain(backtrace(false))]`
 //!
-//!     - Chainable links: Returns `None`
-//!     - Foreign links: Forwards to the foreign error's implementation of `::std::error::Error::cause()`
-//!     - Custom links: Returns `None`
+//!     Disable backtrace functionality in the generated code. This should be kept in sync with the value of the `backtrace` feature of the `error-chain` crate.
+//!     In other words, if you set `backtrace = "false"` here, you must also specify `default-features = false` for `error-chain` in your `Cargo.toml`
 //!
-//!     When the `use_extern_macros` feature is enabled, the value does not need to be stringified:
+//!     Since the error chain state is then trivially constructible, the generated `Error` also gets a `const fn from_kind_const()` constructor,
+//!     so that errors can be defined in `const` items. It is not generated with the `location` or `spantrace` attributes, since those capture
+//!     information at runtime. If the `ErrorKind` has generic parameters with trait bounds, this requires Rust 1.61 or later.
+//!
+//! - `#[error_chain(backtrace_env = "MYAPP_BACKTRACE")]`
+//!
+//!     Check the given environment variable instead of `RUST_BACKTRACE` to decide whether to capture a backtrace when an `Error` is constructed
+//!     or chained, such as for applications that namespace their environment variables. Like with `RUST_BACKTRACE`, a backtrace is captured
+//!     if the variable is set to anything other than `0`. Unlike `error-chain`, the variable is checked every time rather than only once.
+//!     This requires the `backtrace` feature of the `error-chain` crate, and can't be used with `backtrace = "false"`.
+//!
+//! - `#[error_chain(state = "MyState")]`
+//!
+//!     Store a value of the given type in every `Error`, such as to attach a request ID or a timestamp to it, and return it from
+//!     `Error::state()` and `Error::state_mut()`. The value is stored in the last extra field of the `Error`, after the ones for `location`
+//!     and `spantrace`, if any. It does not replace the `error_chain::State` that holds the error chain and the backtrace,
+//!     since that is what the `error_chain::ChainedError` impl and the errors that link this one expect.
+//!
+//!     The type must implement `Default`, which is used to construct the value whenever an `Error` is constructed, chained or converted
+//!     from a linked error, as well as `Debug` unless the `error_debug` attribute is used, and `Send` and `'static` like the rest of the `Error`.
+//!     The `from_kind_const()` constructor is not generated, since `Default::default()` can't be called in a `const` context.
 //!
 //!     ```
-//!     # #![feature(use_extern_macros)]
+//!     # #[macro_use] extern crate derive_error_chain;
+//!     #
+//!     #[derive(Debug, Default)]
+//!     pub struct RequestState {
+//!         request_id: Option<u64>,
+//!     }
+//!
+//!     #[derive(Debug, ErrorChain)]
+//!     #[error_chain(state = "RequestState")]
+//!     pub enum ErrorKind {
+//!         Msg(String),
+//!     }
+//!
+//!     fn handle(request_id: u64) -> Result<()> {
+//!         let mut err: Error = "request failed".into();
+//!         err.state_mut().request_id = Some(request_id);
+//!         Err(err)
+//!     }
 //!     #
+//!     # fn main() {
+//!     #     assert_eq!(handle(5).unwrap_err().state().request_id, Some(5));
+//!     # }
+//!     ```
+//!
+//! # Variant definitions
+//!
+//! - Chainable links
+//!
+//!     ```
 //!     # #[macro_use] extern crate derive_error_chain;
 //!     #
+//!     # mod other_error {
+//!     #     #[derive(Debug, ErrorChain)]
+//!     #     pub enum ErrorKind {
+//!     #         Msg(String),
+//!     #     }
+//!     # }
+//!     #
 //!     # #[derive(Debug, ErrorChain)]
 //!     # pub enum ErrorKind {
-//!         # #[error_chain(custom)]
-//!     #[error_chain(cause = |_, err| err)]
-//!     Io(::std::path::PathBuf, ::std::io::Error),
+//!     #[error_chain(link = "other_error::Error")]
+//!     Another(other_error::ErrorKind),
 //!     # }
 //!     ```
 //!
+//!     A chainable link is an error and errorkind that have been generated using `error-chain` or `derive-error-chain`. The variant must have a single field
+//!     to hold the chained errorkind, and the `link` attribute must specify a path to the chained error.
+//!
+//!     When the `use_extern_macros` feature is enabled, the value of the `link` attribute does not need to be stringified:
+//!
 //!     ```
 //!     # #![feature(use_extern_macros)]
 //!     #
 //!     # #[macro_use] extern crate derive_error_chain;
 //!     #
+//!     # mod other_error {
+//!     #     #[derive(Debug, ErrorChain)]
+//!     #     pub enum ErrorKind {
+//!     #         Msg(String),
+//!     #     }
+//!     # }
+//!     #
 //!     # #[derive(Debug, ErrorChain)]
 //!     # pub enum ErrorKind {
-//!         # #[error_chain(custom)]
-//!     #[error_chain(cause = parse_file_error_cause)]
-//!     Io(::std::path::PathBuf, ::std::io::Error),
+//!     #[error_chain(link = other_error::Error)]
+//!     Another(other_error::ErrorKind),
 //!     # }
+//!     ```
+//!
+//! - Foreign links
+//!
+//!     ```
+//!     # #[macro_use] extern crate derive_error_chain;
 //!     #
-//!     # fn parse_file_error_cause<'a>(_: &::std::path::Path, err: &'a ::std::io::Error) -> &'a ::std::error::Error {
-//!     #     err
+//!     # #[derive(Debug, ErrorChain)]
+//!     # pub enum ErrorKind {
+//!     #[error_chain(foreign)]
+//!     Fmt(::std::fmt::Error),
 //!     # }
 //!     ```
 //!
-//! # Conflicts with `error-chain` macros when the `use_extern_macros` feature is enabled
+//!     A foreign link is an error that implements `::std::error::Error` but otherwise does not follow `error-chain`'s conventions. The variant must have
+//!     a single field to hold the foreign error. This can be either a tuple field as above or a named field:
 //!
-//! If you have the `use_extern_macros` feature enabled and have code like this:
+//!     ```
+//!     # #[macro_use] extern crate derive_error_chain;
+//!     #
+//!     # #[derive(Debug, ErrorChain)]
+//!     # pub enum ErrorKind {
+//!     #[error_chain(foreign)]
+//!     Io { source: ::std::io::Error },
+//!     # }
+//!     ```
 //!
-//! ```compile_fail
-//! #![feature(use_extern_macros)]
+//!     The foreign error can also be a boxed trait object like `Box<::std::error::Error + Send + Sync>`, to hold any kind of error.
+//!     Its description, display and cause are then forwarded to the boxed error. Similarly, the foreign error can be held in an `Arc`,
+//!     like `::std::sync::Arc<::std::io::Error>`, so that the `ErrorKind` can share it with other owners.
 //!
-//! #[macro_use] extern crate derive_error_chain;
-//! #[macro_use] extern crate error_chain; // Want to use `bail!` and `quick_main!`
+//!     The boxed error must be `Send`, as must every other field of the `ErrorKind`, since `error_chain::ChainedError` requires the generated `Error`
+//!     to be `Send`. This applies even if the `Error` is never chained, so a `Box<::std::error::Error>` that is not `Send` can't be used:
 //!
-//! #[derive(Debug, ErrorChain)]
-//! #[error_chain(result = "MyResult")]
-//! enum ErrorKind {
-//!     Msg(String),
-//! }
+//!     ```compile_fail
+//!     # #[macro_use] extern crate derive_error_chain;
+//!     #
+//!     # #[derive(Debug, ErrorChain)]
+//!     # pub enum ErrorKind {
+//!     #[error_chain(foreign)]
+//!     Other(Box<::std::error::Error>),
+//!     # }
+//!     #
+//!     # fn main() { }
+//!     ```
 //!
-//! quick_main!(|| -> MyResult<()> {
-//!     bail!("failed");
-//! });
-//! ```
+//!     The variant can also have other fields, as long as they're all marked with `#[error_chain(default)]`. The foreign error is then the one field
+//!     that isn't marked, and the `From` impl for the foreign error sets the other fields to their `Default::default()` values:
 //!
-//! it'll fail to compile with:
+//!     ```
+//!     # #[macro_use] extern crate derive_error_chain;
+//!     #
+//!     # #[derive(Debug, ErrorChain)]
+//!     # pub enum ErrorKind {
+//!     #[error_chain(foreign)]
+//!     Io { source: ::std::io::Error, #[error_chain(default)] retries: u32 },
+//!     # }
+//!     ```
 //!
-//! ```text,ignore
-//! error: macro `error_chain` may not be used in attributes
-//! ```
+//!     If the type of the foreign error is a type parameter of the `ErrorKind`, no `From` impl is generated for it, since `impl<T> From<T> for Error<T>`
+//!     would usually conflict with the other `From` impls of the `Error`, such as the ones for `&str` and `String` for the `Msg` variant.
+//!     So `?` can't be used to convert such a foreign error. If the `From` impl would not conflict, such as when the `ErrorKind` has no `Msg` variant
+//!     and no other links, it can be generated anyway with `force_from`:
 //!
-//! This is because the compiler thinks `#[error_chain(result = "MyResult")]` is the invocation of an attribute macro, notices that `error_chain!` is
-//! a `macro_rules` macro brought into scope from the `error-chain` crate, and thus complains that a `macro_rules` macro cannot be used as
-//! an attribute macro. It does this even though there is no attribute macro named `error_chain` and that the custom derive from this crate
-//! has registered `error_chain` as an attribute it supports.
+//!     ```
+//!     # #[macro_use] extern crate derive_error_chain;
+//!     #
+//!     #[derive(Debug, ErrorChain)]
+//!     pub enum ErrorKind<T: ::std::error::Error + Send + 'static> {
+//!         #[error_chain(foreign, force_from)]
+//!         Foreign(T),
+//!     }
+//!     #
+//!     # fn main() {
+//!     #     let _: Error<::std::fmt::Error> = ::std::fmt::Error.into();
+//!     # }
+//!     ```
 //!
-//! See <https://github.com/rust-lang/rust/issues/38356#issuecomment-324277403> for the discussion.
+//!     If the `ErrorKind` has any foreign links, the generated `Error` has an `into_foreign::<T>()` method that consumes it and returns
+//!     the foreign error by value if the `Error` is a foreign link holding a `T`, or the `Error` itself otherwise. A boxed trait object
+//!     can't be returned this way, since it isn't a concrete error type.
 //!
-//! To work around this, don't use `#[macro_use]` with the `error-chain` crate. Instead, either `use` the macros you need from it:
+//!     ```
+//!     # #[macro_use] extern crate derive_error_chain;
+//!     #
+//!     #[derive(Debug, ErrorChain)]
+//!     pub enum ErrorKind {
+//!         Msg(String),
 //!
-//! ```
-//! #![feature(use_extern_macros)]
+//!         #[error_chain(foreign)]
+//!         Io(::std::io::Error),
+//!     }
+//!     #
+//!     # fn main() {
+//!     let err: Error = ::std::io::Error::new(::std::io::ErrorKind::NotFound, "file not found").into();
+//!     let err: ::std::io::Error = err.into_foreign().unwrap();
+//!     assert_eq!(err.kind(), ::std::io::ErrorKind::NotFound);
+//!     # }
+//!     ```
 //!
-//! #[macro_use] extern crate derive_error_chain;
-//! extern crate error_chain;
+//! - Custom links
 //!
-//! use error_chain::{ bail, quick_main };
+//!     ```
+//!     # #[macro_use] extern crate derive_error_chain;
+//!     #
+//!     # #[derive(Debug, ErrorChain)]
+//!     # pub enum ErrorKind {
+//!     #[error_chain(custom)]
+//!     InvalidToolchainName(String),
+//!     # }
+//!     ```
 //!
-//! #[derive(Debug, ErrorChain)]
-//! #[error_chain(result = "MyResult")]
-//! enum ErrorKind {
-//!     Msg(String),
-//! }
+//!     A custom link is an arbitrary variant that can hold any members.
 //!
-//! quick_main!(|| -> MyResult<()> {
-//!     bail!("failed");
-//! });
+//! A variant must be exactly one kind of link. Declaring different link types for the same variant, whether in one attribute or in separate ones,
+//! is an error that names all the declared link types:
+//!
+//! ```compile_fail
+//! # #[macro_use] extern crate derive_error_chain;
+//! #
+//! # #[derive(Debug, ErrorChain)]
+//! # pub enum ErrorKind {
+//! #[error_chain(custom)]
+//! #[error_chain(foreign)]
+//! Io(::std::io::Error),
+//! # }
 //! ```
 //!
-//! or fully qualify their paths:
+//! fails with "Member Io has conflicting link types `custom` and `foreign`."
 //!
-//! ```
-//! #![feature(use_extern_macros)]
+//! # Variant attributes
 //!
-//! #[macro_use] extern crate derive_error_chain;
-//! extern crate error_chain;
+//! In addition to the above attributes that identify the type of the variant's link, the below attributes can be used on all links.
 //!
-//! #[derive(Debug, ErrorChain)]
-//! #[error_chain(result = "MyResult")]
-//! enum ErrorKind {
-//!     Msg(String),
-//! }
+//! - `#[error_chain(description = "some_function_expression")]`
 //!
-//! error_chain::quick_main!(|| -> MyResult<()> {
-//!     error_chain::bail!("failed");
-//! });
-//! ```
+//!     Specifies a function expression to be used to implement `ErrorKind::description()`.
+//!     This value is also returned from the implementation of `::std::error::Error::description()` on the generated `Error`.
 //!
-//! `use`ing the `error_chain!` macro itself is more complicated: it must be renamed so that it doesn't just cause the above error again,
-//! and other macros it uses must also be imported, even though they're an implementation detail:
+//!     This can be an inline lambda:
 //!
-//! ```
-//! #![feature(use_extern_macros)]
+//!     ```
+//!     # #[macro_use] extern crate derive_error_chain;
+//!     #
+//!     # #[derive(Debug, ErrorChain)]
+//!     # pub enum ErrorKind {
+//!         # #[error_chain(custom)]
+//!     #[error_chain(description = r#"|_| "invalid toolchain name""#)]
+//!     InvalidToolchainName(String),
+//!     # }
+//!     ```
 //!
-//! #[macro_use] extern crate derive_error_chain;
-//! extern crate error_chain;
+//!     or it can be a separate function:
 //!
-//! use error_chain::{ error_chain as error_chain_macro, error_chain_processing, impl_error_chain_kind, impl_error_chain_processed, impl_extract_backtrace };
+//!     ```
+//!     # #[macro_use] extern crate derive_error_chain;
+//!     #
+//!     # #[derive(Debug, ErrorChain)]
+//!     # pub enum ErrorKind {
+//!         # #[error_chain(custom)]
+//!     #[error_chain(description = "invalid_toolchain_name_error_description")]
+//!     InvalidToolchainName(String),
+//!     # }
 //!
-//! #[derive(Debug, ErrorChain)]
-//! #[error_chain(error = "MyError", result = "MyResult", result_ext = "MyResultExt")]
-//! enum MyErrorKind {
-//!     Msg(String),
-//! }
+//!     // <snip>
 //!
-//! error_chain_macro! {
-//! }
-//! ```
+//!     fn invalid_toolchain_name_error_description(_: &str) -> &str {
+//!         "invalid toolchain name"
+//!     }
+//!     ```
 //!
-//! To use it fully-qualified, the macros it depends on must still be `use`d to bring them into scope:
+//!     The function expression must have the signature `(...) -> &'static str`. It should have one parameter for each field of the variant.
+//!     The fields are passed in by reference.
 //!
-//! ```
-//! #![feature(use_extern_macros)]
+//!     Thus in the above example, since `InvalidToolchainName` had a single field of type `String`, the function expression needed to be of type
+//!     `(&str) -> &'static str`
 //!
-//! #[macro_use] extern crate derive_error_chain;
-//! extern crate error_chain;
+//!     If not specified, the default implementation behaves in this way:
 //!
-//! use error_chain::{ error_chain_processing, impl_error_chain_kind, impl_error_chain_processed, impl_extract_backtrace };
+//!     - Chainable links: Forwards to the chained error kind's `description()`
+//!     - Foreign links: Forwards to the foreign error's implementation of `::std::error::Error::description()`
+//!     - Custom links: Returns the stringified name of the variant.
 //!
-//! #[derive(Debug, ErrorChain)]
-//! #[error_chain(error = "MyError", result = "MyResult", result_ext = "MyResultExt")]
-//! enum MyErrorKind {
-//!     Msg(String),
-//! }
+//!     When the `use_extern_macros` feature is enabled, the value does not need to be stringified:
 //!
-//! error_chain::error_chain! {
-//! }
-//! ```
+//!     ```
+//!     # #![feature(use_extern_macros)]
+//!     #
+//!     # #[macro_use] extern crate derive_error_chain;
+//!     #
+//!     # #[derive(Debug, ErrorChain)]
+//!     # pub enum ErrorKind {
+//!         # #[error_chain(custom)]
+//!     #[error_chain(description = |_| "invalid toolchain name")]
+//!     InvalidToolchainName(String),
+//!     # }
+//!     ```
 //!
-//! It's possible this experience will be made better before the `use_extern_macros` feature stabilizes.
+//!     ```
+//!     # #![feature(use_extern_macros)]
+//!     #
+//!     # #[macro_use] extern crate derive_error_chain;
+//!     #
+//!     # #[derive(Debug, ErrorChain)]
+//!     # pub enum ErrorKind {
+//!         # #[error_chain(custom)]
+//!     #[error_chain(description = invalid_toolchain_name_error_description)]
+//!     InvalidToolchainName(String),
+//!     # }
+//!     #
+//!     # fn invalid_toolchain_name_error_description(_: &str) -> &str {
+//!     #     "invalid toolchain name"
+//!     # }
+//!     ```
+//!
+//!     When the `use_extern_macros` feature is enabled, closure expressions that only call `write!` on the `::std::fmt::Formatter` can instead use a shorthand:
+//!
+//!     ```
+//!     # #![feature(use_extern_macros)]
+//!     #
+//!     # #[macro_use] extern crate derive_error_chain;
+//!     #
+//!     # #[derive(Debug, ErrorChain)]
+//!     # pub enum ErrorKind {
+//!         # #[error_chain(custom)]
+//!     #[error_chain(description = const("invalid toolchain name"))]
+//!     InvalidToolchainName(String),
+//!     # }
+//!     ```
+//!
+//!     Since the description is returned as-is, this format string cannot contain any parameters. Use the `display` attribute for that instead.
+//!     Escaped braces like `{{` and `}}` are still unescaped, so `const("100% {{done}}")` returns `100% {done}`.
+//!
+//!     ```compile_fail
+//!     # #![feature(use_extern_macros)]
+//!     #
+//!     # #[macro_use] extern crate derive_error_chain;
+//!     #
+//!     # #[derive(Debug, ErrorChain)]
+//!     # pub enum ErrorKind {
+//!         # #[error_chain(custom)]
+//!     #[error_chain(description = const("invalid toolchain name: '{0}'"))]
+//!     InvalidToolchainName(String),
+//!     # }
+//!     ```
+//!
+//! - `#[error_chain(display = "some_function_expression")]`
+//!
+//!     Specifies a function expression to be used to implement `::std::fmt::Display::fmt()` on the `ErrorKind` and generated `Error`
+//!
+//!     This can be an inline lambda:
+//!
+//!     ```
+//!     # #[macro_use] extern crate derive_error_chain;
+//!     #
+//!     # #[derive(Debug, ErrorChain)]
+//!     # pub enum ErrorKind {
+//!         # #[error_chain(custom)]
+//!     #[error_chain(display = r#"|t| write!(f, "invalid toolchain name: '{}'", t)"#)]
+//!     InvalidToolchainName(String),
+//!     # }
+//!     ```
+//!
+//!     or it can be a separate function:
+//!
+//!     ```
+//!     # #[macro_use] extern crate derive_error_chain;
+//!     #
+//!     # #[derive(Debug, ErrorChain)]
+//!     # pub enum ErrorKind {
+//!         # #[error_chain(custom)]
+//!     #[error_chain(display = "invalid_toolchain_name_error_display")]
+//!     InvalidToolchainName(String),
+//!     # }
+//!
+//!     // <snip>
+//!
+//!     fn invalid_toolchain_name_error_display(f: &mut ::std::fmt::Formatter, t: &str) -> ::std::fmt::Result {
+//!         write!(f, "invalid toolchain name: '{}'", t)
+//!     }
+//!     ```
+//!
+//!     The function expression must have the signature `(&mut ::std::fmt::Formatter, ...) -> ::std::fmt::Result`.
+//!     It should have one `&mut ::std::fmt::Formatter` parameter, and one parameter for each field of the variant. The fields are passed in by reference.
+//!     For brevity, closure expressions do not need the `&mut ::std::fmt::Formatter` parameter and instead capture `f` from the closure environment.
+//!     Closures can also have block bodies with multiple statements, and can refer to functions and constants in scope of the `ErrorKind`.
+//!     The types of their parameters are inferred from the fields, so they can be dereferenced or have methods called on them without type annotations.
+//!
+//!     Thus in the above example, since `InvalidToolchainName` had a single field of type `String`, the function expression needed to be of type
+//!     `(&mut ::std::fmt::Formatter, &str) -> ::std::fmt::Result`
+//!
+//!     If not specified, the default implementation of `::std::fmt::Display::fmt()` behaves in this way:
+//!
+//!     - Chainable links: Forwards to the chained errorkind's implementation of `::std::fmt::Display::fmt()`
+//!     - Foreign links: Forwards to the foreign error's implementation of `::std::fmt::Display::fmt()`
+//!     - Custom links: Writes the description of the variant to the formatter.
+//!
+//!     When the `use_extern_macros` feature is enabled, the value does not need to be stringified:
+//!
+//!     ```
+//!     # #![feature(use_extern_macros)]
+//!     #
+//!     # #[macro_use] extern crate derive_error_chain;
+//!     #
+//!     # #[derive(Debug, ErrorChain)]
+//!     # pub enum ErrorKind {
+//!         # #[error_chain(custom)]
+//!     #[error_chain(display = |t| write!(f, "invalid toolchain name: '{}'", t))]
+//!     InvalidToolchainName(String),
+//!     # }
+//!     ```
+//!
+//!     ```
+//!     # #![feature(use_extern_macros)]
+//!     #
+//!     # #[macro_use] extern crate derive_error_chain;
+//!     #
+//!     # #[derive(Debug, ErrorChain)]
+//!     # pub enum ErrorKind {
+//!         # #[error_chain(custom)]
+//!     #[error_chain(display = invalid_toolchain_name_error_display)]
+//!     InvalidToolchainName(String),
+//!     # }
+//!     #
+//!     # fn invalid_toolchain_name_error_display(f: &mut ::std::fmt::Formatter, t: &str) -> ::std::fmt::Result {
+//!     #     write!(f, "invalid toolchain name: '{}'", t)
+//!     # }
+//!     ```
+//!
+//!     When the `use_extern_macros` feature is enabled, closure expressions that only call `write!` on the `::std::fmt::Formatter` can instead use a shorthand:
+//!
+//!     ```
+//!     # #![feature(use_extern_macros)]
+//!     #
+//!     # #[macro_use] extern crate derive_error_chain;
+//!     #
+//!     # #[derive(Debug, ErrorChain)]
+//!     # pub enum ErrorKind {
+//!     // Tuple variants use `{0}`, `{1}`, and so on
+//!         # #[error_chain(custom)]
+//!     #[error_chain(display = const("invalid toolchain name: '{0}'"))]
+//!     InvalidToolchainName(String),
+//!     # }
+//!     ```
+//!
+//!     ```
+//!     # #![feature(use_extern_macros)]
+//!     #
+//!     # #[macro_use] extern crate derive_error_chain;
+//!     #
+//!     # #[derive(Debug, ErrorChain)]
+//!     # pub enum ErrorKind {
+//!     // Struct variants use `{name_of_the_field}`
+//!         # #[error_chain(custom)]
+//!     #[error_chain(display = const("invalid toolchain name: '{name}'"))]
+//!     InvalidToolchainName { name: String },
+//!     # }
+//!     ```
+//!
+//!     `{description}` refers to the description of the member, unless the member has a field with that name, in which case it's an error.
+//!
+//!     ```
+//!     # #![feature(use_extern_macros)]
+//!     #
+//!     # #[macro_use] extern crate derive_error_chain;
+//!     #
+//!     # #[derive(Debug, ErrorChain)]
+//!     # pub enum ErrorKind {
+//!         # #[error_chain(custom)]
+//!     #[error_chain(description = const("invalid toolchain name"))]
+//!     #[error_chain(display = const("{description}: '{0}'"))]
+//!     InvalidToolchainName(String),
+//!     # }
+//!     ```
+//!
+//! - `#[error_chain(context = "format string")]`
+//!
+//!     For foreign links, specifies a format string to be used to implement `::std::fmt::Display::fmt()` instead of forwarding to the foreign error,
+//!     such as to add some context to it. The foreign error is referenced in the same way as the fields of a `display = const("...")` shorthand,
+//!     ie as `{0}` for tuple variants and `{name_of_the_field}` for struct variants. `::std::error::Error::cause()` still forwards to
+//!     the foreign error's implementation. This cannot be combined with the `display` attribute.
+//!
+//!     ```
+//!     # #[macro_use] extern crate derive_error_chain;
+//!     #
+//!     # #[derive(Debug, ErrorChain)]
+//!     # pub enum ErrorKind {
+//!     #[error_chain(foreign, context = "reading config: {0}")]
+//!     Io(::std::io::Error),
+//!     # }
+//!     ```
+//!
+//!     When the `use_extern_macros` feature is enabled, the value can also be written as `context = const("reading config: {0}")`,
+//!     or be any function expression like the `display` attribute.
+//!
+//! - `#[error_chain(cause = "some_function_expression")]`
+//!
+//!     Specifies a function expression to be used to implement `::std::fmt::Error::cause()` on the generated `Error`
+//!
+//!     This can be an inline lambda:
+//!
+//!     ```
+//!     # #[macro_use] extern crate derive_error_chain;
+//!     #
+//!     # #[derive(Debug, ErrorChain)]
+//!     # pub enum ErrorKind {
+//!         # #[error_chain(custom)]
+//!     #[error_chain(cause = "|_, err| err")]
+//!     Io(::std::path::PathBuf, ::std::io::Error),
+//!     # }
+//!     ```
+//!
+//!     or it can be a separate function:
+//!
+//!     ```
+//!     # #[macro_use] extern crate derive_error_chain;
+//!     #
+//!     # #[derive(Debug, ErrorChain)]
+//!     # pub enum ErrorKind {
+//!         # #[error_chain(custom)]
+//!     #[error_chain(cause = "parse_file_error_cause")]
+//!     Io(::std::path::PathBuf, ::std::io::Error),
+//!     # }
+//!
+//!     // <snip>
+//!
+//!     fn parse_file_error_cause<'a>(_: &::std::path::Path, err: &'a ::std::io::Error) -> &'a ::std::error::Error {
+//!         err
+//!     }
+//!     ```
+//!
+//!     The function expression must have the signature `(...) -> &::std::error::Error`. It should have one parameter for each field of the variant.
+//!     The fields are passed in by reference. The result is wrapped in `Option::Some()` for returning from `::std::error::Error::cause()`
+//!
+//!     Thus in the above example, since `Io` had two fields of type `::std::path::PathBuf` and `::std::io::Error`, the function expression needed to be of type
+//!     `(&::std::path::Path, &::std::io::Error) -> &::std::error::Error`
+//!
+//!     If not specified, the default implementation of `::std::error::Error::cause()` behaves in this way:
+//!
+//!     - Chainable links: Returns `None`
+//!     - Foreign links: Forwards to the foreign error's implementation of `::std::error::Error::cause()`
+//!     - Custom links: Returns the field marked with `#[error_chain(source)]` if there is one, otherwise `None`
+//!
+//!     Similarly, the generated `::std::error::Error::source()` forwards to the foreign error's implementation of `::std::error::Error::source()`
+//!     for foreign links without a `cause` attribute, since modern errors often only implement `source()`.
+//!
+//!     When the `use_extern_macros` feature is enabled, the value does not need to be stringified:
+//!
+//!     ```
+//!     # #![feature(use_extern_macros)]
+//!     #
+//!     # #[macro_use] extern crate derive_error_chain;
+//!     #
+//!     # #[derive(Debug, ErrorChain)]
+//!     # pub enum ErrorKind {
+//!         # #[error_chain(custom)]
+//!     #[error_chain(cause = |_, err| err)]
+//!     Io(::std::path::PathBuf, ::std::io::Error),
+//!     # }
+//!     ```
+//!
+//!     ```
+//!     # #![feature(use_extern_macros)]
+//!     #
+//!     # #[macro_use] extern crate derive_error_chain;
+//!     #
+//!     # #[derive(Debug, ErrorChain)]
+//!     # pub enum ErrorKind {
+//!         # #[error_chain(custom)]
+//!     #[error_chain(cause = parse_file_error_cause)]
+//!     Io(::std::path::PathBuf, ::std::io::Error),
+//!     # }
+//!     #
+//!     # fn parse_file_error_cause<'a>(_: &::std::path::Path, err: &'a ::std::io::Error) -> &'a ::std::error::Error {
+//!     #     err
+//!     # }
+//!     ```
+//!
+//! - `#[error_chain(extract)]`
+//!
+//!     For foreign and custom links, implement `::std::convert::TryFrom<Error>` for the type of the variant's field, or a tuple of the types
+//!     of its fields if it has more than one. The conversion returns the fields if the error's kind is this variant, and the original error otherwise.
+//!     The fields' types must not be type parameters of the `ErrorKind`, and `TryFrom` requires Rust 1.34 or later.
+//!
+//!     ```
+//!     # #[macro_use] extern crate derive_error_chain;
+//!     #
+//!     # use std::convert::TryFrom;
+//!     #
+//!     #[derive(Debug, ErrorChain)]
+//!     pub enum ErrorKind {
+//!         Msg(String),
+//!
+//!         #[error_chain(custom, extract)]
+//!         HttpStatus(u32),
+//!     }
+//!     #
+//!     # fn main() {
+//!     #     assert_eq!(u32::try_from(Error::from(ErrorKind::HttpStatus(404))).unwrap(), 404);
+//!     #     assert!(u32::try_from(Error::from("Oops")).is_err());
+//!     # }
+//!     ```
+//!
+//! - `#[error_chain(source)]` on a field
+//!
+//!     For custom links, marks the field that holds the underlying error. It is returned from `::std::error::Error::cause()` and
+//!     `::std::error::Error::source()` of the generated `Error`, so it does not need to be written out with the `cause` attribute.
+//!     The field's type must implement `::std::error::Error + 'static`. At most one field of a variant can be marked,
+//!     and it cannot be combined with the `cause` attribute.
+//!
+//!     ```
+//!     # #[macro_use] extern crate derive_error_chain;
+//!     #
+//!     # #[derive(Debug, ErrorChain)]
+//!     # pub enum ErrorKind {
+//!     #[error_chain(custom)]
+//!     Request { url: String, #[error_chain(source)] err: ::std::io::Error },
+//!     # }
+//!     ```
+//!
+//!     The field can also be chosen by its index with the `source_field` attribute on the variant instead:
+//!
+//!     ```
+//!     # #[macro_use] extern crate derive_error_chain;
+//!     #
+//!     # #[derive(Debug, ErrorChain)]
+//!     # pub enum ErrorKind {
+//!     #[error_chain(custom, source_field = 0)]
+//!     Wrapped(::std::io::Error),
+//!     # }
+//!     ```
+//!
+//! - `#[from]` on a field
+//!
+//!     For custom links, generates a `From` impl for the field's type on the generated `Error`, like for a foreign link. This eases migrating
+//!     from `thiserror`, which has the same attribute. Any other fields of the variant must be marked with `#[error_chain(default)]`, and are set
+//!     to their `Default::default()` values by the `From` impl. Unless the variant has a `cause` attribute or another `source` field,
+//!     the field is also the source of the error, as if it was marked with `#[error_chain(source)]`. At most one field of a variant can be marked.
+//!
+//!     ```
+//!     # #[macro_use] extern crate derive_error_chain;
+//!     #
+//!     #[derive(Debug, ErrorChain)]
+//!     pub enum ErrorKind {
+//!         Msg(String),
+//!
+//!         #[error_chain(custom)]
+//!         Io(#[from] ::std::io::Error),
+//!     }
+//!     #
+//!     # fn main() {
+//!     fn read() -> Result<()> {
+//!         Err(::std::io::Error::new(::std::io::ErrorKind::NotFound, "file not found"))?
+//!     }
+//!     #     assert!(read().is_err());
+//!     # }
+//!     ```
+//!
+//! # Conflicts with `error-chain` macros when the `use_extern_macros` feature is enabled
+//!
+//! If you have the `use_extern_macros` feature enabled and have code like this:
+//!
+//! ```compile_fail
+//! #![feature(use_extern_macros)]
+//!
+//! #[macro_use] extern crate derive_error_chain;
+//! #[macro_use] extern crate error_chain; // Want to use `bail!` and `quick_main!`
+//!
+//! #[derive(Debug, ErrorChain)]
+//! #[error_chain(result = "MyResult")]
+//! enum ErrorKind {
+//!     Msg(String),
+//! }
+//!
+//! quick_main!(|| -> MyResult<()> {
+//!     bail!("failed");
+//! });
+//! ```
+//!
+//! it'll fail to compile with:
+//!
+//! ```text,ignore
+//! error: macro `error_chain` may not be used in attributes
+//! ```
+//!
+//! This is because the compiler thinks `#[error_chain(result = "MyResult")]` is the invocation of an attribute macro, notices that `error_chain!` is
+//! a `macro_rules` macro brought into scope from the `error-chain` crate, and thus complains that a `macro_rules` macro cannot be used as
+//! an attribute macro. It does this even though there is no attribute macro named `error_chain` and that the custom derive from this crate
+//! has registered `error_chain` as an attribute it supports.
+//!
+//! See <https://github.com/rust-lang/rust/issues/38356#issuecomment-324277403> for the discussion.
+//!
+//! To work around this, don't use `#[macro_use]` with the `error-chain` crate. Instead, either `use` the macros you need from it:
+//!
+//! ```
+//! #![feature(use_extern_macros)]
+//!
+//! #[macro_use] extern crate derive_error_chain;
+//! extern crate error_chain;
+//!
+//! use error_chain::{ bail, quick_main };
+//!
+//! #[derive(Debug, ErrorChain)]
+//! #[error_chain(result = "MyResult")]
+//! enum ErrorKind {
+//!     Msg(String),
+//! }
+//!
+//! quick_main!(|| -> MyResult<()> {
+//!     bail!("failed");
+//! });
+//! ```
+//!
+//! or fully qualify their paths:
+//!
+//! ```
+//! #![feature(use_extern_macros)]
+//!
+//! #[macro_use] extern crate derive_error_chain;
+//! extern crate error_chain;
+//!
+//! #[derive(Debug, ErrorChain)]
+//! #[error_chain(result = "MyResult")]
+//! enum ErrorKind {
+//!     Msg(String),
+//! }
+//!
+//! error_chain::quick_main!(|| -> MyResult<()> {
+//!     error_chain::bail!("failed");
+//! });
+//! ```
+//!
+//! `use`ing the `error_chain!` macro itself is more complicated: it must be renamed so that it doesn't just cause the above error again,
+//! and other macros it uses must also be imported, even though they're an implementation detail:
+//!
+//! ```
+//! #![feature(use_extern_macros)]
+//!
+//! #[macro_use] extern crate derive_error_chain;
+//! extern crate error_chain;
+//!
+//! use error_chain::{ error_chain as error_chain_macro, error_chain_processing, impl_error_chain_kind, impl_error_chain_processed, impl_extract_backtrace };
+//!
+//! #[derive(Debug, ErrorChain)]
+//! #[error_chain(error = "MyError", result = "MyResult", result_ext = "MyResultExt")]
+//! enum MyErrorKind {
+//!     Msg(String),
+//! }
+//!
+//! error_chain_macro! {
+//! }
+//! ```
+//!
+//! To use it fully-qualified, the macros it depends on must still be `use`d to bring them into scope:
+//!
+//! ```
+//! #![feature(use_extern_macros)]
+//!
+//! #[macro_use] extern crate derive_error_chain;
+//! extern crate error_chain;
+//!
+//! use error_chain::{ error_chain_processing, impl_error_chain_kind, impl_error_chain_processed, impl_extract_backtrace };
+//!
+//! #[derive(Debug, ErrorChain)]
+//! #[error_chain(error = "MyError", result = "MyResult", result_ext = "MyResultExt")]
+//! enum MyErrorKind {
+//!     Msg(String),
+//! }
+//!
+//! error_chain::error_chain! {
+//! }
+//! ```
+//!
+//! It's possible this experience will be made better before the `use_extern_macros` feature stabilizes.
+
+extern crate proc_macro;
+extern crate proc_macro2;
+#[macro_use]
+extern crate quote;
+#[macro_use]
+extern crate syn;
+extern crate syntex_fmt_macros;
+
+#[proc_macro_derive(ErrorChain, attributes(error_chain, from))]
+pub fn derive_error_chain(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+	let ast: syn::DeriveInput = syn::parse(input).unwrap();
+
+	let (impl_generics, ty_generics, where_clause) = ast.generics.split_for_impl();
+
+	// Names of the generic parameters used by the generated code, chosen to not conflict with the generic parameters of the errorkind
+	let generic_t = unique_ident("__T", &ast.generics);
+	let generic_e = unique_ident("__E", &ast.generics);
+	let generic_f = unique_ident("__F", &ast.generics);
+	let generic_k = unique_ident("__K", &ast.generics);
+	let generic_ek = unique_ident("__EK", &ast.generics);
+	let generic_d = unique_ident("__D", &ast.generics);
+	let generic_h = unique_ident("__H", &ast.generics);
+	let lifetime_a = syn::Lifetime::new(&format!("'{}", unique_ident("__a", &ast.generics)), proc_macro2::Span::call_site());
+
+	let mut generics_lifetime = ast.generics.clone();
+	generics_lifetime.params = std::iter::once(parse_quote!(#lifetime_a)).chain(generics_lifetime.params).collect();
+	let (impl_generics_lifetime, _, _) = generics_lifetime.split_for_impl();
+
+	let generic_params = &ast.generics.params;
+
+	// Generic parameters of the errorkind along with their bounds and defaults, for the definition of the error struct
+	let struct_generics = &ast.generics;
+
+	let mut result_ext_generics_t = ast.generics.clone();
+	result_ext_generics_t.params.push(parse_quote!(#generic_t));
+	let (result_ext_impl_generics_t, result_ext_ty_generics_t, _) = result_ext_generics_t.split_for_impl();
+
+	let mut result_ext_generics_t_e = result_ext_generics_t.clone();
+	result_ext_generics_t_e.params.push(parse_quote!(#generic_e: ::std::error::Error + ::std::marker::Send + 'static));
+	let (result_ext_impl_generics_t_e, _, _) = result_ext_generics_t_e.split_for_impl();
+
+	let generics: std::collections::HashSet<_> =
+		ast.generics.params.iter()
+		.filter_map(|param|
+			if let syn::GenericParam::Type(syn::TypeParam { ref ident, .. }) = *param {
+				Some(ident)
+			}
+			else {
+				None
+			})
+		.collect();
+
+	let TopLevelProperties {
+		error_kind_name,
+		error_kind_vis,
+		error_name,
+		result_ext_name,
+		result_name,
+		result_error,
+		result_default,
+		result_ok_default,
+		error_partial_eq,
+		error_eq,
+		error_hash,
+		error_doc,
+		result_doc,
+		result_ext_doc,
+		prelude_name,
+		module_name,
+		location,
+		from_boxed,
+		into_boxed,
+		into_io,
+		display,
+		description,
+		must_use,
+		error_repr,
+		error_debug,
+		chain_delimiter,
+		from_unit,
+		state,
+		opaque,
+		ord,
+		dynamic_description,
+		no_error_description,
+		spantrace,
+		anyhow,
+		no_from,
+		predicates,
+		debug_display,
+		strict_msg,
+		constructors,
+		backtrace_env,
+		from_str,
+		run,
+		bail_name,
+		chain_err_name,
+		support_backtrace,
+		error_chain_name,
+	} = (&ast).into();
+
+	// With `module`, the generated items are one module deeper than the `ErrorKind`, so their visibility has to be adjusted to stay the same.
+	let module_vis = error_kind_vis.clone();
+	// A `macro_rules!` macro defined in the module is only visible after it with `#[macro_use]`
+	let module_macro_use = if bail_name.is_some() { Some(quote!(#[macro_use])) } else { None };
+	let error_kind_vis: syn::Visibility = match module_name {
+		Some(_) => syn::parse2(child_module_visibility(&error_kind_vis)).unwrap(),
+		None => error_kind_vis,
+	};
+
+	// `error_chain::ChainedError` requires the error to be `'static`, so it and everything that depends on it must be bounded accordingly
+	// when the errorkind has lifetime parameters.
+	let mut static_generics = ast.generics.clone();
+	if ast.generics.lifetimes().next().is_some() {
+		static_generics.make_where_clause().predicates.push(parse_quote!(#error_name #ty_generics: 'static));
+	}
+	let (_, _, static_where_clause) = static_generics.split_for_impl();
+
+	// Applies to every field of the `Error`, including the extra ones below
+	let error_field_vis = if opaque { None } else { Some(quote!(pub)) };
+
+	// `error_chain::State` has no room for the location, so it's stored in an extra field of the error instead.
+	let (track_caller, location_field, location_arg) =
+		if location {
+			(
+				Some(quote!(#[track_caller])),
+				Some(quote! {
+					/// The source location where this error was constructed or chained.
+					#error_field_vis &'static ::std::panic::Location<'static>,
+				}),
+				Some(quote!(, ::std::panic::Location::caller())),
+			)
+		}
+		else {
+			(None, None, None)
+		};
+
+	// Similarly, the span trace is stored in another extra field after the location.
+	let (tracing_error_extern_crate, spantrace_field, spantrace_arg, spantrace_fn) =
+		if spantrace {
+			let tracing_error_name = proc_macro2::Ident::new(&format!("{}_tracing_error", error_name), proc_macro2::Span::call_site());
+			let spantrace_index = syn::Index::from(if location { 3 } else { 2 });
+
+			(
+				Some(quote!(extern crate tracing_error as #tracing_error_name;)),
+				Some(quote! {
+					/// The span trace captured when this error was constructed or chained.
+					#error_field_vis #tracing_error_name::SpanTrace,
+				}),
+				Some(quote!(, #tracing_error_name::SpanTrace::capture())),
+				Some(quote! {
+					/// Returns the span trace captured when this error was constructed or chained.
+					pub fn spantrace(&self) -> &#tracing_error_name::SpanTrace {
+						&self.#spantrace_index
+					}
+				}),
+			)
+		}
+		else {
+			(None, None, None, None)
+		};
+
+	// `error_chain::State::default()` and `State::new()` only check `RUST_BACKTRACE`, so with a custom variable the state is constructed directly
+	// with a backtrace captured the same way. Like `State::new()`, a chained error's backtrace is reused if it has one.
+	if backtrace_env.is_some() && !support_backtrace {
+		panic!("`backtrace_env` can't be used with `backtrace = \"false\"`, since the backtrace is then not captured.");
+	}
+
+	let (default_state, chained_state_box_e, chained_state_error) =
+		if let Some(ref backtrace_env) = backtrace_env {
+			let capture_backtrace = quote! {
+				match ::std::env::var_os(#backtrace_env) {
+					Some(ref value) if value != "0" => Some(::std::sync::Arc::new(#error_chain_name::Backtrace::new())),
+					_ => None,
+				}
+			};
+
+			(
+				quote!(#error_chain_name::State { next_error: None, backtrace: #capture_backtrace }),
+				quote!({
+					let error: Box<::std::error::Error + Send> = Box::new(e);
+					let backtrace = <#error_name #ty_generics as #error_chain_name::ChainedError>::extract_backtrace(&*error).or_else(|| #capture_backtrace);
+					#error_chain_name::State { next_error: Some(error), backtrace }
+				}),
+				quote!({
+					let backtrace = <Self as #error_chain_name::ChainedError>::extract_backtrace(&*error).or_else(|| #capture_backtrace);
+					#error_chain_name::State { next_error: Some(error), backtrace }
+				}),
+			)
+		}
+		else {
+			(
+				quote!(#error_chain_name::State::default()),
+				quote!(#error_chain_name::State::new::<#error_name #ty_generics>(Box::new(e))),
+				quote!(#error_chain_name::State::new::<Self>(error)),
+			)
+		};
+
+	// `error_chain::State` can't be replaced, since it's what `ChainedError::new()` takes and what the errors that link this one
+	// read the next error and backtrace from, so a user-supplied state is stored in the last extra field instead.
+	let (state_field, state_arg, state_fns) =
+		if let Some(ref state) = state {
+			let state_index = syn::Index::from(2 + location as usize + spantrace as usize);
+
+			(
+				Some(quote! {
+					/// The user-supplied state of this error.
+					#error_field_vis #state,
+				}),
+				Some(quote!(, <#state as ::std::default::Default>::default())),
+				Some(quote! {
+					/// Returns the user-supplied state of this error.
+					pub fn state(&self) -> &#state {
+						&self.#state_index
+					}
+
+					/// Returns a mutable reference to the user-supplied state of this error.
+					pub fn state_mut(&mut self) -> &mut #state {
+						&mut self.#state_index
+					}
+				}),
+			)
+		}
+		else {
+			(None, None, None)
+		};
+
+	let extra_args = quote!(#location_arg #spantrace_arg #state_arg);
+
+	let result = match ast.data {
+		syn::Data::Enum(syn::DataEnum { variants, .. }) => {
+			let links: Vec<Link> = variants.into_iter().map(Into::into).collect();
+
+			if strict_msg {
+				match links.first() {
+					Some(&Link { link_type: LinkType::Msg, .. }) => (),
+					_ => panic!("`strict_msg` requires the first member of the {} to be `Msg(String)`, like with `error_chain!`.", error_kind_name),
+				}
+			}
+
+			let error_kind_description_cases = links.iter().map(|link| link.error_kind_description(&error_kind_name, description.as_ref(), dynamic_description));
+
+			let error_kind_name_cases = links.iter().map(|link| link.error_kind_name_case(&error_kind_name));
+
+			let error_kind_code_fn = if links.iter().any(|link| link.discriminant.is_some()) {
+				let error_kind_code_cases = links.iter().map(|link| link.error_kind_code_case(&error_kind_name));
+
+				Some(quote! {
+					/// The explicit discriminant of the error kind's variant, if it has one.
+					pub fn code(&self) -> Option<i64> {
+						match *self {
+							#(#error_kind_code_cases)*
+						}
+					}
+				})
+			}
+			else {
+				None
+			};
+
+			let (error_kind_predicate_fns, error_predicate_fns) = if predicates {
+				let mut predicate_names = std::collections::HashSet::new();
+
+				let (error_kind_predicate_fns, error_predicate_fns): (Vec<_>, Vec<_>) = links.iter().map(|link| {
+					let variant_ident = &link.variant_ident;
+					let pattern = fields_pattern_ignore(&link.variant_fields);
+					let predicate_name = proc_macro2::Ident::new(&format!("is_{}", to_snake_case(&unraw_name(variant_ident))), proc_macro2::Span::call_site());
+					let allow_deprecated = link.allow_deprecated();
+
+					if !predicate_names.insert(predicate_name.to_string()) {
+						panic!("`predicates` would generate more than one method named {} for the {}", predicate_name, error_kind_name);
+					}
+
+					let doc_comment = format!("Returns `true` if the error kind is `{}::{}`.", error_kind_name, unraw_name(variant_ident));
+					let error_doc_comment = format!("Returns `true` if the kind of this error is `{}::{}`.", error_kind_name, unraw_name(variant_ident));
+
+					(
+						quote! {
+							#[doc = #doc_comment]
+							#allow_deprecated
+							pub fn #predicate_name(&self) -> bool {
+								#[allow(unreachable_patterns)]
+								match *self {
+									#error_kind_name::#variant_ident #pattern => true,
+									_ => false,
+								}
+							}
+						},
+						quote! {
+							#[doc = #error_doc_comment]
+							pub fn #predicate_name(&self) -> bool {
+								self.0.#predicate_name()
+							}
+						},
+					)
+				}).unzip();
+
+				(Some(quote!(#(#error_kind_predicate_fns)*)), Some(quote!(#(#error_predicate_fns)*)))
+			}
+			else {
+				(None, None)
+			};
+
+			let error_constructor_fns = if constructors {
+				let mut constructor_names = std::collections::HashSet::new();
+
+				let error_constructor_fns: Vec<_> = links.iter().map(|link| {
+					let variant_ident = &link.variant_ident;
+					let constructor_name = to_snake_case(&unraw_name(variant_ident));
+					let constructor_name: proc_macro2::Ident = syn::parse_str(&constructor_name).unwrap_or_else(|_|
+						panic!("`constructors` would generate a method named {} for the {}, which is not a valid identifier", constructor_name, error_kind_name));
+
+					if !constructor_names.insert(constructor_name.to_string()) {
+						panic!("`constructors` would generate more than one method named {} for the {}", constructor_name, error_kind_name);
+					}
+
+					let field_names: Vec<_> = link.variant_fields.iter().enumerate().map(|(i, field)| match field.ident {
+						Some(ref ident) => ident.clone(),
+						None => proc_macro2::Ident::new(&format!("value{}", i), proc_macro2::Span::call_site()),
+					}).collect();
+					let field_names = &field_names;
+					let field_tys = link.variant_fields.iter().map(|field| &field.ty);
+
+					let fields = match link.variant_fields {
+						syn::Fields::Named(_) => quote!({ #(#field_names),* }),
+						syn::Fields::Unnamed(_) => quote!((#(#field_names),*)),
+						syn::Fields::Unit => quote!(),
+					};
+
+					let doc_comment = format!("Constructs an error of kind `{}::{}`, and generates a backtrace.", error_kind_name, unraw_name(variant_ident));
+					let allow_deprecated = link.allow_deprecated();
+
+					quote! {
+						#[doc = #doc_comment]
+						#allow_deprecated
+						#track_caller
+						pub fn #constructor_name(#(#field_names: #field_tys),*) -> Self {
+							Self::from_kind(#error_kind_name::#variant_ident #fields)
+						}
+					}
+				}).collect();
+
+				Some(quote!(#(#error_constructor_fns)*))
+			}
+			else {
+				None
+			};
+
+			let error_kind_display_cases = links.iter().map(|link| link.error_kind_display_case(&error_kind_name, display.as_ref(), dynamic_description, debug_display));
+
+			let error_kind_from_impls =
+				links.iter().filter(|_| !no_from).filter_map(|link|
+					link.error_kind_from_impl(
+						&error_kind_name,
+						&lifetime_a, &impl_generics, &impl_generics_lifetime, &ty_generics, where_clause,
+					));
+
+			let error_cause_cases = links.iter().filter_map(|link| link.error_cause_case(&error_kind_name));
+
+			let error_source_cases = links.iter().filter_map(|link| link.error_source_case(&error_kind_name));
+
+			let error_doc_comment = error_doc.unwrap_or_else(|| format!(r"The Error type.
+
+This struct is made of three things:
+
+- `{0}` which is used to determine the type of the error.
+- a backtrace, generated when the error is created.
+- an error chain, used for the implementation of `Error::cause()`.", error_kind_name));
+
+			let error_must_use = if must_use {
+				Some(quote!(#[must_use = "this Error must be handled"]))
+			}
+			else {
+				None
+			};
+
+			let error_repr = error_repr.map(|error_repr| quote!(#[repr(#error_repr)]));
+
+			let (error_derive_debug, error_debug_impl) = match error_debug {
+				Some(error_debug) => {
+					let call =
+						if is_closure(&error_debug) {
+							call_closure(&error_debug, quote!(self), 1)
+						}
+						else {
+							quote!(#error_debug(f, self))
+						};
+
+					(None, Some(quote! {
+						impl #impl_generics ::std::fmt::Debug for #error_name #ty_generics #where_clause {
+							fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+								#call
+							}
+						}
+					}))
+				},
+
+				None => (Some(quote!(#[derive(Debug)])), None),
+			};
+
+			let error_from_impls =
+				links.iter().filter(|_| !no_from).filter_map(|link|
+					link.error_from_impl(
+						&error_kind_name, &error_name,
+						&generics,
+						&track_caller, &extra_args,
+						&lifetime_a, &impl_generics, &impl_generics_lifetime, &ty_generics, where_clause,
+					));
+
+			let error_try_from_impls =
+				links.iter().filter(|link| link.extract).map(|link|
+					link.error_try_from_impl(&error_kind_name, &error_name, &impl_generics, &ty_generics, where_clause));
+
+			let has_msg = links.iter().any(|link| link.variant_ident == "Msg");
+
+			let error_context_impl = if has_msg {
+				Some(quote! {
+					#[allow(unused)]
+					impl #impl_generics #error_name #ty_generics #where_clause {
+						/// Extends the error chain with a new `Msg` entry made from the given context.
+						#track_caller
+						pub fn context<#generic_d>(self, context: #generic_d) -> Self
+							where #generic_d: ::std::fmt::Display, Self: 'static
+						{
+							#error_name::with_chain(self, #error_kind_name::Msg(context.to_string().into()))
+						}
+					}
+				})
+			}
+			else {
+				None
+			};
+
+			let error_from_boxed_impl = if from_boxed {
+				if !has_msg {
+					panic!("`from_boxed` requires the {} to have a `Msg(String)` variant.", error_kind_name);
+				}
+
+				Some(quote! {
+					impl #impl_generics From<Box<::std::error::Error + Send + Sync>> for #error_name #ty_generics #static_where_clause {
+						#track_caller
+						fn from(err: Box<::std::error::Error + Send + Sync>) -> Self {
+							let message = err.to_string();
+							#error_name::with_boxed_chain(err, #error_kind_name::Msg(message.into()))
+						}
+					}
+				})
+			}
+			else {
+				None
+			};
+
+			let bail_macro = bail_name.map(|bail_name| {
+				if !has_msg {
+					panic!("`bail` requires the {} to have a `Msg(String)` variant.", error_kind_name);
+				}
+
+				quote! {
+					/// Exits the function early with an error made from the given format string and arguments.
+					#[allow(unused_macros)]
+					macro_rules! #bail_name {
+						($($arg:tt)*) => {
+							return ::std::result::Result::Err(#error_name::from_kind(#error_kind_name::Msg(format!($($arg)*).into())).into())
+						};
+					}
+				}
+			});
+
+			let run_fn = if run && result_name.is_some() {
+				let mut run_generics = static_generics.clone();
+				run_generics.params.push(parse_quote!(#generic_f: FnOnce() -> ::std::result::Result<(), #error_name #ty_generics>));
+				let (run_impl_generics, _, run_where_clause) = run_generics.split_for_impl();
+
+				Some(quote! {
+					/// Runs the given function and returns the exit code for the process, like `error_chain`'s `quick_main!`.
+					///
+					/// If the function fails, its error and the whole error chain are printed to stderr and the exit code is 1. Otherwise it is 0.
+					#error_kind_vis fn run #run_impl_generics (f: #generic_f) -> i32 #run_where_clause {
+						match f() {
+							Ok(()) => 0,
+							Err(ref err) => {
+								eprint!("{}", #error_chain_name::ChainedError::display_chain(err));
+								1
+							},
+						}
+					}
+				})
+			}
+			else {
+				None
+			};
+
+			let from_unit_impls = from_unit.map(|from_unit| {
+				match links.iter().find(|link| link.variant_ident == from_unit) {
+					Some(link) => match link.variant_fields {
+						syn::Fields::Unit => (),
+						_ => panic!("`from_unit` names member {} but it has fields.", from_unit),
+					},
+
+					None => panic!("`from_unit` names member {} but the {} has no such member.", from_unit, error_kind_name),
+				}
+
+				quote! {
+					impl #impl_generics From<()> for #error_kind_name #ty_generics #where_clause {
+						fn from(_: ()) -> Self { #error_kind_name::#from_unit }
+					}
+
+					impl #impl_generics From<()> for #error_name #ty_generics #where_clause {
+						#track_caller
+						fn from(_: ()) -> Self { Self::from_kind(#error_kind_name::#from_unit) }
+					}
+				}
+			});
+
+			let from_str_impl = if from_str {
+				let mut msg_link = None;
+				for link in &links {
+					if let LinkType::Msg = link.link_type {
+						msg_link = Some(link);
+						break;
+					}
+				}
+				let msg_link = msg_link.unwrap_or_else(|| panic!("`from_str` requires the {} to have a `Msg(String)` member.", error_kind_name));
+				let msg_ident = &msg_link.variant_ident;
+				let allow_deprecated = msg_link.allow_deprecated();
+
+				Some(quote! {
+					#allow_deprecated
+					impl #impl_generics ::std::str::FromStr for #error_kind_name #ty_generics #where_clause {
+						type Err = ::std::convert::Infallible;
+
+						fn from_str(s: &str) -> ::std::result::Result<Self, Self::Err> { Ok(#error_kind_name::#msg_ident(s.into())) }
+					}
+				})
+			}
+			else {
+				None
+			};
+
+			// The snapshot is only needed by the conversions into `Send + Sync` errors
+			let sync_snapshot_fn = if into_boxed || into_io || anyhow {
+				Some(quote! {
+					/// Returns a copy of this error and its chain that is `Send + Sync`, for use with APIs that require it.
+					///
+					/// Since the errors in the chain are not necessarily `Sync`, each of them is replaced by its `::std::fmt::Display` message.
+					pub fn sync_snapshot(&self) -> Box<::std::error::Error + Send + Sync> where Self: 'static {
+						#[derive(Debug)]
+						struct Snapshot {
+							message: String,
+							source: Option<Box<Snapshot>>,
+						}
+
+						impl ::std::fmt::Display for Snapshot {
+							fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+								::std::fmt::Display::fmt(&self.message, f)
+							}
+						}
+
+						impl ::std::error::Error for Snapshot {
+							fn description(&self) -> &str { &self.message }
+
+							fn source(&self) -> Option<&(::std::error::Error + 'static)> {
+								match self.source {
+									Some(ref source) => Some(&**source),
+									None => None,
+								}
+							}
+						}
+
+						fn snapshot(err: &(::std::error::Error + 'static)) -> Snapshot {
+							Snapshot {
+								message: err.to_string(),
+								source: err.source().map(|source| Box::new(snapshot(source))),
+							}
+						}
+
+						Box::new(snapshot(self))
+					}
+				})
+			}
+			else {
+				None
+			};
+
+			let error_kind_into_boxed_impl = if into_boxed {
+				let mut into_boxed_generics = ast.generics.clone();
+				into_boxed_generics.make_where_clause().predicates.push(parse_quote!(#error_name #ty_generics: 'static));
+				let (_, _, into_boxed_where_clause) = into_boxed_generics.split_for_impl();
+
+				Some(quote! {
+					impl #impl_generics From<#error_kind_name #ty_generics> for Box<::std::error::Error + Send + Sync> #into_boxed_where_clause {
+						#track_caller
+						fn from(kind: #error_kind_name #ty_generics) -> Self {
+							#error_name::from_kind(kind).sync_snapshot()
+						}
+					}
+				})
+			}
+			else {
+				None
+			};
+
+			let error_into_io_impl = if into_io {
+				let mut into_io_generics = ast.generics.clone();
+				into_io_generics.make_where_clause().predicates.push(parse_quote!(#error_name #ty_generics: 'static));
+				let (_, _, into_io_where_clause) = into_io_generics.split_for_impl();
+
+				Some(quote! {
+					impl #impl_generics From<#error_name #ty_generics> for ::std::io::Error #into_io_where_clause {
+						fn from(err: #error_name #ty_generics) -> Self {
+							::std::io::Error::new(::std::io::ErrorKind::Other, err.sync_snapshot())
+						}
+					}
+				})
+			}
+			else {
+				None
+			};
+
+			// `From<Error>` can't be implemented for `anyhow::Error` since it would conflict with anyhow's blanket impl for all `Send + Sync` errors,
+			// because coherence doesn't know that `error_chain::State` will never be `Sync`. So the `Error` gets an `into_anyhow()` method instead.
+			let (anyhow_extern_crate, anyhow_fn, anyhow_impls) = if anyhow {
+				let anyhow_name = proc_macro2::Ident::new(&format!("{}_anyhow", error_name), proc_macro2::Span::call_site());
+
+				let mut anyhow_generics = ast.generics.clone();
+				anyhow_generics.make_where_clause().predicates.push(parse_quote!(#error_name #ty_generics: 'static));
+				let (_, _, anyhow_where_clause) = anyhow_generics.split_for_impl();
+
+				(
+					Some(quote!(extern crate anyhow as #anyhow_name;)),
+					Some(quote! {
+						/// Converts this error into an `anyhow::Error` with the same messages and chain.
+						///
+						/// Since the generated `Error` is not `Sync`, the `anyhow::Error` is made from the snapshot returned by `sync_snapshot()`.
+						pub fn into_anyhow(self) -> #anyhow_name::Error where Self: 'static {
+							#anyhow_name::Error::from_boxed(self.sync_snapshot())
+						}
+					}),
+					Some(quote! {
+						impl #impl_generics From<#error_kind_name #ty_generics> for #anyhow_name::Error #anyhow_where_clause {
+							#track_caller
+							fn from(kind: #error_kind_name #ty_generics) -> Self {
+								#error_name::from_kind(kind).into_anyhow()
+							}
+						}
+					}),
+				)
+			}
+			else {
+				(None, None, None)
+			};
+
+			let take_source_fns = quote! {
+				/// Consumes this error and returns the next error in the chain, if any.
+				///
+				/// This is useful to re-wrap the chained error in a different error type.
+				pub fn into_source(mut self) -> Option<Box<::std::error::Error + Send>> {
+					self.take_source()
+				}
+
+				/// Removes the next error in the chain from this error and returns it, if any. This error is left without a chained error.
+				pub fn take_source(&mut self) -> Option<Box<::std::error::Error + Send>> {
+					self.1.next_error.take()
+				}
+			};
+
+			let source_chain_fns = quote! {
+				/// Iterates over the error chain by repeatedly calling `::std::error::Error::source()`, starting with this error.
+				pub fn source_chain(&self) -> impl Iterator<Item = &(::std::error::Error + 'static)> where Self: 'static {
+					::std::iter::successors(Some(self as &(::std::error::Error + 'static)), |err| err.source())
+				}
+
+				/// Iterates over the causes of this error, that is the error chain returned by `source_chain()` without this error itself.
+				pub fn causes(&self) -> impl Iterator<Item = &(::std::error::Error + 'static)> where Self: 'static {
+					self.source_chain().skip(1)
+				}
+
+				/// Iterates over the error chain, starting with this error. This is the same as `source_chain()`, and is named like
+				/// `anyhow::Error::chain()` to ease migrating from `anyhow`.
+				pub fn chain(&self) -> impl Iterator<Item = &(::std::error::Error + 'static)> where Self: 'static {
+					self.source_chain()
+				}
+			};
+
+			let map_kind_fn = quote! {
+				/// Returns a mutable reference to the kind of the error, to modify it in place while keeping the error chain and the backtrace.
+				pub fn kind_mut(&mut self) -> &mut #error_kind_name #ty_generics { &mut self.0 }
+
+				/// Replaces the kind of this error with the result of the given function, keeping the error chain and the backtrace.
+				///
+				/// This is useful to enrich the kind with more context without chaining a new error.
+				pub fn map_kind<#generic_f: FnOnce(#error_kind_name #ty_generics) -> #error_kind_name #ty_generics>(mut self, f: #generic_f) -> Self {
+					self.0 = f(self.0);
+					self
+				}
+			};
+
+			let const_constructor_fn = if !support_backtrace && !location && !spantrace && state.is_none() {
+				Some(quote! {
+					/// Constructs an error from a kind in a `const` context. The error has no backtrace and no chained error.
+					pub const fn from_kind_const(kind: #error_kind_name #ty_generics) -> Self {
+						#error_name(kind, #error_chain_name::State { next_error: None })
+					}
+				})
+			}
+			else {
+				None
+			};
+
+			let extract_backtrace_fn = if support_backtrace {
+				// Several members can link the same error type, but it only needs to be downcast to once
+				let mut chained_error_tys = std::collections::HashSet::new();
+				let chained_error_extract_backtrace_cases = links.iter()
+					.filter(|link| match link.chainable_error_ty() {
+						Some(error_ty) => chained_error_tys.insert(quote!(#error_ty).to_string()),
+						None => true,
+					})
+					.filter_map(Link::chained_error_extract_backtrace_case);
+
+				Some(quote! {
+					fn extract_backtrace(err: &(::std::error::Error + Send + 'static)) -> Option<::std::sync::Arc<#error_chain_name::Backtrace>> {
+						if let Some(err) = err.downcast_ref::<Self>() {
+							return err.1.backtrace.clone();
+						}
+
+						#(#chained_error_extract_backtrace_cases)*
+
+						None
+					}
+				})
+			}
+			else {
+				None
+			};
 
-extern crate proc_macro;
-extern crate proc_macro2;
-#[macro_use]
-extern crate quote;
-#[macro_use]
-extern crate syn;
-extern crate syntex_fmt_macros;
+			// Walking the chain downcasts the errors in it to `Self`, which needs `Self: 'static`. Rather than adding that bound to `backtrace()`,
+			// the fallback is only generated when the `Error` is always `'static`.
+			let backtrace_fn = if support_backtrace && ast.generics.lifetimes().next().is_none() && ast.generics.type_params().next().is_none() {
+				let mut chained_error_tys = std::collections::HashSet::new();
+				let chained_error_backtrace_cases = links.iter()
+					.filter(|link| match link.chainable_error_ty() {
+						Some(error_ty) => chained_error_tys.insert(quote!(#error_ty).to_string()),
+						None => true,
+					})
+					.filter_map(Link::chained_error_backtrace_case);
 
-#[proc_macro_derive(ErrorChain, attributes(error_chain))]
-pub fn derive_error_chain(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
-	let ast: syn::DeriveInput = syn::parse(input).unwrap();
+				quote! {
+					/// Returns the backtrace associated with this error.
+					///
+					/// If this error doesn't have a backtrace, the backtrace of the first error in its chain that has one is returned instead.
+					pub fn backtrace(&self) -> Option<&#error_chain_name::Backtrace> {
+						if let Some(backtrace) = self.1.backtrace() {
+							return Some(backtrace);
+						}
 
-	let (impl_generics, ty_generics, where_clause) = ast.generics.split_for_impl();
+						let mut next = match self.1.next_error {
+							Some(ref err) => Some(&**err as &(::std::error::Error + 'static)),
+							None => None,
+						};
 
-	let mut generics_lifetime = ast.generics.clone();
-	generics_lifetime.params = std::iter::once(parse_quote!('__a)).chain(generics_lifetime.params).collect();
-	let (impl_generics_lifetime, _, _) = generics_lifetime.split_for_impl();
+						while let Some(err) = next {
+							if let Some(err) = err.downcast_ref::<Self>() {
+								if let Some(backtrace) = err.1.backtrace() {
+									return Some(backtrace);
+								}
+							}
 
-	let mut result_generics = ast.generics.clone();
-	result_generics.params.push(parse_quote!(__T));
-	let (_, result_ty_generics, _) = result_generics.split_for_impl();
+							#(#chained_error_backtrace_cases)*
 
-	let mut result_ext_generics_t = ast.generics.clone();
-	result_ext_generics_t.params.push(parse_quote!(__T));
-	let (result_ext_impl_generics_t, result_ext_ty_generics_t, _) = result_ext_generics_t.split_for_impl();
+							next = err.source();
+						}
 
-	let mut result_ext_generics_t_e = result_ext_generics_t.clone();
-	result_ext_generics_t_e.params.push(parse_quote!(__E: ::std::error::Error + ::std::marker::Send + 'static));
-	let (result_ext_impl_generics_t_e, _, _) = result_ext_generics_t_e.split_for_impl();
+						None
+					}
+				}
+			}
+			else {
+				quote! {
+					/// Returns the backtrace associated with this error.
+					pub fn backtrace(&self) -> Option<&#error_chain_name::Backtrace> {
+						self.1.backtrace()
+					}
+				}
+			};
 
-	let generics: std::collections::HashSet<_> =
-		ast.generics.params.iter()
-		.filter_map(|param|
-			if let syn::GenericParam::Type(syn::TypeParam { ref ident, .. }) = *param {
-				Some(ident)
+			let result_ext_chain_err_doc_comment = format!("\
+				If the `Result` is an `Err` then `{}` evaluates the closure, \
+				which returns *some type that can be converted to `{}`*, \
+				boxes the original error to store as the cause, then returns a new error \
+				containing the original error.\
+			", chain_err_name, error_kind_name);
+
+			let result_ext_chain_err_kind_doc_comment = format!("\
+				If the `Result` is an `Err` then `chain_err_kind` converts the given kind, \
+				which is *some type that can be converted to `{}`*, \
+				boxes the original error to store as the cause, then returns a new error \
+				containing the original error.\
+			", error_kind_name);
+
+			let result_ext_ok_or_kind_doc_comment = format!("\
+				If the `Option` is `None` then `ok_or_kind` converts the given kind, \
+				which is *some type that can be converted to `{}`*, \
+				then returns a new error of that kind, like `Option::ok_or`. \
+				If the `Result` is an `Err` then it behaves like `chain_err_kind`.\
+			", error_kind_name);
+
+			let error_partial_eq_impl = if error_partial_eq {
+				let mut partial_eq_generics = ast.generics.clone();
+				partial_eq_generics.make_where_clause().predicates.push(parse_quote!(#error_kind_name #ty_generics: PartialEq));
+				let (_, _, partial_eq_where_clause) = partial_eq_generics.split_for_impl();
+
+				Some(quote! {
+					impl #impl_generics PartialEq for #error_name #ty_generics #partial_eq_where_clause {
+						fn eq(&self, other: &Self) -> bool {
+							self.0 == other.0
+						}
+					}
+				})
 			}
 			else {
 				None
-			})
-		.collect();
+			};
 
-	let TopLevelProperties {
-		error_kind_name,
-		error_kind_vis,
-		error_name,
-		result_ext_name,
-		result_name,
-		support_backtrace,
-		error_chain_name,
-	} = (&ast).into();
+			let error_eq_impl = if error_eq {
+				if !error_partial_eq {
+					panic!("`error_eq` requires `error_partial_eq`, since `Eq` requires `PartialEq`.");
+				}
 
-	let result = match ast.data {
-		syn::Data::Enum(syn::DataEnum { variants, .. }) => {
-			let links: Vec<Link> = variants.into_iter().map(Into::into).collect();
+				let mut eq_generics = ast.generics.clone();
+				eq_generics.make_where_clause().predicates.push(parse_quote!(#error_kind_name #ty_generics: Eq));
+				let (_, _, eq_where_clause) = eq_generics.split_for_impl();
 
-			let error_kind_description_cases = links.iter().map(|link| link.error_kind_description(&error_kind_name));
+				Some(quote! {
+					impl #impl_generics Eq for #error_name #ty_generics #eq_where_clause { }
+				})
+			}
+			else {
+				None
+			};
 
-			let error_kind_display_cases = links.iter().map(|link| link.error_kind_display_case(&error_kind_name));
+			let error_hash_impl = if error_hash {
+				let mut hash_generics = ast.generics.clone();
+				hash_generics.make_where_clause().predicates.push(parse_quote!(#error_kind_name #ty_generics: ::std::hash::Hash));
+				let (_, _, hash_where_clause) = hash_generics.split_for_impl();
 
-			let error_kind_from_impls =
-				links.iter().filter_map(|link|
-					link.error_kind_from_impl(
-						&error_kind_name,
-						&impl_generics, &impl_generics_lifetime, &ty_generics, where_clause,
-					));
+				Some(quote! {
+					impl #impl_generics ::std::hash::Hash for #error_name #ty_generics #hash_where_clause {
+						fn hash<#generic_h: ::std::hash::Hasher>(&self, state: &mut #generic_h) {
+							::std::hash::Hash::hash(&self.0, state)
+						}
+					}
+				})
+			}
+			else {
+				None
+			};
 
-			let error_cause_cases = links.iter().filter_map(|link| link.error_cause_case(&error_kind_name));
+			let error_kind_ord_impl = if ord {
+				let mut ord_generics = ast.generics.clone();
+				for param in generic_params {
+					if let syn::GenericParam::Type(syn::TypeParam { ref ident, .. }) = *param {
+						ord_generics.make_where_clause().predicates.push(parse_quote!(#ident: ::std::cmp::Ord));
+					}
+				}
+				let (_, _, ord_where_clause) = ord_generics.split_for_impl();
 
-			let error_doc_comment = format!(r"The Error type.
+				let error_kind_index_cases = links.iter().enumerate().map(|(index, link)| link.error_kind_index_case(&error_kind_name, index));
+				let error_kind_cmp_cases = links.iter().map(|link| link.error_kind_cmp_case(&error_kind_name));
 
-This struct is made of three things:
+				Some(quote! {
+					impl #impl_generics ::std::cmp::PartialOrd for #error_kind_name #ty_generics #ord_where_clause {
+						fn partial_cmp(&self, other: &Self) -> Option<::std::cmp::Ordering> {
+							Some(::std::cmp::Ord::cmp(self, other))
+						}
+					}
 
-- `{0}` which is used to determine the type of the error.
-- a backtrace, generated when the error is created.
-- an error chain, used for the implementation of `Error::cause()`.", error_kind_name);
+					impl #impl_generics ::std::cmp::Ord for #error_kind_name #ty_generics #ord_where_clause {
+						fn cmp(&self, other: &Self) -> ::std::cmp::Ordering {
+							let index = |kind: &Self| -> usize {
+								match *kind {
+									#(#error_kind_index_cases)*
+								}
+							};
 
-			let error_from_impls =
-				links.iter().filter_map(|link|
-					link.error_from_impl(
-						&error_kind_name, &error_name,
-						&generics,
-						&impl_generics, &impl_generics_lifetime, &ty_generics, where_clause,
-					));
+							#[allow(unreachable_patterns)]
+							match (self, other) {
+								#(#error_kind_cmp_cases)*
 
-			let extract_backtrace_fn = if support_backtrace {
-				let chained_error_extract_backtrace_cases = links.iter().filter_map(Link::chained_error_extract_backtrace_case);
+								_ => ::std::cmp::Ord::cmp(&index(self), &index(other)),
+							}
+						}
+					}
+				})
+			}
+			else {
+				None
+			};
+
+			let prelude_mod = prelude_name.map(|prelude_name| {
+				let prelude_vis = child_module_visibility(&error_kind_vis);
+				let result_name = result_name.iter();
+				let result_ext_name = result_ext_name.iter();
+
+				quote! {
+					/// Re-exports the generated types, for glob-importing.
+					#error_kind_vis mod #prelude_name {
+						#prelude_vis use super::{ #error_name, #error_kind_name #(, #result_name)* #(, #result_ext_name)* };
+					}
+				}
+			});
+
+			let result_ext_doc_comment = result_ext_doc.unwrap_or_else(|| "Additional methods for `Result` and `Option`, for easy interaction with this crate.".to_string());
+
+			// `::std::error::Error::description()` can't return a `Cow`, so the deprecated default is used for dynamic descriptions instead
+			let (description_ty, error_description_fn) =
+				if dynamic_description {
+					(quote!(::std::borrow::Cow<str>), None)
+				}
+				else if no_error_description {
+					(quote!(&str), None)
+				}
+				else {
+					(quote!(&str), Some(quote!(fn description(&self) -> &str { self.0.description() })))
+				};
 
+			let location_fn = if location {
 				Some(quote! {
-					fn extract_backtrace(err: &(::std::error::Error + Send + 'static)) -> Option<::std::sync::Arc<#error_chain_name::Backtrace>> {
-						if let Some(err) = err.downcast_ref::<Self>() {
-							return err.1.backtrace.clone();
+					/// Returns the source location where this error was constructed or chained.
+					pub fn location(&self) -> &'static ::std::panic::Location<'static> {
+						self.2
+					}
+				})
+			}
+			else {
+				None
+			};
+
+			let into_foreign_fn = if links.iter().any(|link| link.foreign_error_ty().is_some()) {
+				let foreign_links: Vec<_> = links.iter().filter(|link| link.foreign_error_ty().is_some()).collect();
+
+				let is_t_cases = foreign_links.iter().map(|link| {
+					let variant_ident = &link.variant_ident;
+					let pattern = single_field_pattern(&link.variant_fields, quote!(ref err));
+					let allow_deprecated = link.allow_deprecated();
+
+					quote! {
+						#allow_deprecated
+						#error_kind_name::#variant_ident #pattern => (err as &::std::any::Any).is::<#generic_t>(),
+					}
+				});
+
+				let into_t_cases = foreign_links.iter().map(|link| {
+					let variant_ident = &link.variant_ident;
+					let pattern = single_field_pattern(&link.variant_fields, quote!(err));
+					let allow_deprecated = link.allow_deprecated();
+
+					quote! {
+						#allow_deprecated
+						#error_kind_name::#variant_ident #pattern => {
+							let err: Box<::std::any::Any> = Box::new(err);
+							Ok(*err.downcast::<#generic_t>().unwrap())
+						},
+					}
+				});
+
+				Some(quote! {
+					/// Consumes this error and returns the foreign error it holds of the requested type, if it holds one. Otherwise returns this error back.
+					///
+					/// The error chain and the backtrace are discarded along with the rest of this error.
+					pub fn into_foreign<#generic_t: ::std::error::Error + 'static>(self) -> ::std::result::Result<#generic_t, Self> where Self: 'static {
+						let is_t = match self.0 {
+							#(#is_t_cases)*
+							_ => false,
+						};
+
+						if !is_t {
+							return Err(self);
 						}
 
-						#(#chained_error_extract_backtrace_cases)*
+						match self.0 {
+							#(#into_t_cases)*
+							_ => unreachable!(),
+						}
+					}
+				})
+			}
+			else {
+				None
+			};
 
-						None
+			let chain_iter_kinds_fn = if links.iter().any(|link| link.chainable_error_ty().is_some()) {
+				Some(quote! {
+					/// Iterates over the kinds of this error and of the errors it was chained from.
+					///
+					/// Since the kind of an error that isn't of this type can't be known, the iteration stops at the first such error in the chain,
+					/// such as a foreign error.
+					pub fn chain_iter_kinds(&self) -> impl Iterator<Item = &#error_kind_name #ty_generics> where Self: 'static {
+						::std::iter::successors(Some(self), |err| match err.1.next_error {
+							Some(ref next) => next.downcast_ref::<Self>(),
+							None => None,
+						})
+						.map(#error_name::kind)
 					}
 				})
 			}
@@ -698,29 +2462,172 @@ This struct is made of three things:
 				None
 			};
 
-			let result_ext_chain_err_doc_comment = format!("\
-				If the `Result` is an `Err` then `chain_err` evaluates the closure, \
-				which returns *some type that can be converted to `{}`*, \
-				boxes the original error to store as the cause, then returns a new error \
-				containing the original error.\
-			", error_kind_name);
+			let (location_capture, location_var) =
+				if location {
+					(Some(quote!(let location = ::std::panic::Location::caller();)), Some(quote!(, location)))
+				}
+				else {
+					(None, None)
+				};
+
+			let result_ext_trait = result_ext_name.map(|result_ext_name| quote! {
+				#[doc = #result_ext_doc_comment]
+				#error_kind_vis trait #result_ext_name #result_ext_impl_generics_t #where_clause {
+					#[doc = #result_ext_chain_err_doc_comment]
+					#track_caller
+					fn #chain_err_name<#generic_f, #generic_ek>(self, callback: #generic_f) -> ::std::result::Result<#generic_t, #error_name #ty_generics>
+						where #generic_f: FnOnce() -> #generic_ek, #generic_ek: Into<#error_kind_name #ty_generics>;
+
+					#[doc = #result_ext_chain_err_kind_doc_comment]
+					#track_caller
+					fn chain_err_kind<#generic_ek>(self, kind: #generic_ek) -> ::std::result::Result<#generic_t, #error_name #ty_generics>
+						where #generic_ek: Into<#error_kind_name #ty_generics>;
+
+					#[doc = #result_ext_ok_or_kind_doc_comment]
+					#track_caller
+					fn ok_or_kind<#generic_ek>(self, kind: #generic_ek) -> ::std::result::Result<#generic_t, #error_name #ty_generics>
+						where #generic_ek: Into<#error_kind_name #ty_generics>;
+				}
+
+				impl #result_ext_impl_generics_t_e #result_ext_name #result_ext_ty_generics_t for ::std::result::Result<#generic_t, #generic_e> #static_where_clause {
+					#track_caller
+					fn #chain_err_name<#generic_f, #generic_ek>(self, callback: #generic_f) -> ::std::result::Result<#generic_t, #error_name #ty_generics>
+						where #generic_f: FnOnce() -> #generic_ek, #generic_ek: Into<#error_kind_name #ty_generics> {
+						// Closures can't be `#[track_caller]`, so the location must be captured outside of them
+						#location_capture
+						self.map_err(#[cold] move |e| {
+							let state = #chained_state_box_e;
+							#error_name(callback().into(), state #location_var #spantrace_arg #state_arg)
+						})
+					}
+
+					#track_caller
+					fn chain_err_kind<#generic_ek>(self, kind: #generic_ek) -> ::std::result::Result<#generic_t, #error_name #ty_generics>
+						where #generic_ek: Into<#error_kind_name #ty_generics> {
+						#location_capture
+						self.map_err(#[cold] move |e| {
+							let state = #chained_state_box_e;
+							#error_name(kind.into(), state #location_var #spantrace_arg #state_arg)
+						})
+					}
+
+					#track_caller
+					fn ok_or_kind<#generic_ek>(self, kind: #generic_ek) -> ::std::result::Result<#generic_t, #error_name #ty_generics>
+						where #generic_ek: Into<#error_kind_name #ty_generics> {
+						#result_ext_name::chain_err_kind(self, kind)
+					}
+				}
+
+				impl #result_ext_impl_generics_t #result_ext_name #result_ext_ty_generics_t for ::std::option::Option<#generic_t> #static_where_clause {
+					#track_caller
+					fn #chain_err_name<#generic_f, #generic_ek>(self, callback: #generic_f) -> ::std::result::Result<#generic_t, #error_name #ty_generics>
+						where #generic_f: FnOnce() -> #generic_ek, #generic_ek: Into<#error_kind_name #ty_generics> {
+						#location_capture
+						self.ok_or_else(#[cold] move || {
+							#error_name(callback().into(), #default_state #location_var #spantrace_arg #state_arg)
+						})
+					}
+
+					#track_caller
+					fn chain_err_kind<#generic_ek>(self, kind: #generic_ek) -> ::std::result::Result<#generic_t, #error_name #ty_generics>
+						where #generic_ek: Into<#error_kind_name #ty_generics> {
+						#location_capture
+						self.ok_or_else(#[cold] move || {
+							#error_name(kind.into(), #default_state #location_var #spantrace_arg #state_arg)
+						})
+					}
 
-			let result_wrapper = result_name.map(|result_name| quote! {
-				/// Convenient wrapper around `::std::result::Result`
-				#error_kind_vis type #result_name #result_ty_generics = ::std::result::Result<__T, #error_name #ty_generics>;
+					#track_caller
+					fn ok_or_kind<#generic_ek>(self, kind: #generic_ek) -> ::std::result::Result<#generic_t, #error_name #ty_generics>
+						where #generic_ek: Into<#error_kind_name #ty_generics> {
+						#location_capture
+						self.ok_or_else(#[cold] move || {
+							#error_name(kind.into(), #default_state #location_var #spantrace_arg #state_arg)
+						})
+					}
+				}
+			});
+
+			let result_doc_comment = result_doc.unwrap_or_else(|| "Convenient wrapper around `::std::result::Result`".to_string());
+
+			let result_t_param = if result_ok_default { quote!(#generic_t = ()) } else { quote!(#generic_t) };
+
+			let result_wrapper = result_name.map(|result_name| {
+				if let Some(ref result_error) = result_error {
+					// The error type is pinned, so the alias does not have any of the parameters of the `ErrorKind`
+					return if result_default {
+						quote! {
+							#[doc = #result_doc_comment]
+							#error_kind_vis type #result_name <#result_t_param, #generic_e = #result_error> = ::std::result::Result<#generic_t, #generic_e>;
+						}
+					}
+					else {
+						quote! {
+							#[doc = #result_doc_comment]
+							#error_kind_vis type #result_name <#result_t_param> = ::std::result::Result<#generic_t, #result_error>;
+						}
+					};
+				}
+
+				// Parameters with defaults must come after the `T` parameter of the result, if they're kept at all
+				let result_params = generic_params.iter().filter_map(|param| match *param {
+					syn::GenericParam::Type(syn::TypeParam { default: Some(_), .. }) => None,
+					syn::GenericParam::Type(syn::TypeParam { ref ident, .. }) |
+					syn::GenericParam::Const(syn::ConstParam { ref ident, .. }) => Some(quote!(#ident)),
+					syn::GenericParam::Lifetime(syn::LifetimeDef { ref lifetime, .. }) => Some(quote!(#lifetime)),
+				});
+
+				if result_default {
+					// Parameters with defaults would be unused in the alias, so the default error type uses their defaults directly
+					let error_args = generic_params.iter().map(|param| match *param {
+						syn::GenericParam::Type(syn::TypeParam { default: Some(ref default), .. }) => quote!(#default),
+						syn::GenericParam::Type(syn::TypeParam { ref ident, .. }) |
+						syn::GenericParam::Const(syn::ConstParam { ref ident, .. }) => quote!(#ident),
+						syn::GenericParam::Lifetime(syn::LifetimeDef { ref lifetime, .. }) => quote!(#lifetime),
+					});
+
+					quote! {
+						#[doc = #result_doc_comment]
+						#error_kind_vis type #result_name <#(#result_params,)* #result_t_param, #generic_e = #error_name <#(#error_args),*>> = ::std::result::Result<#generic_t, #generic_e>;
+					}
+				}
+				else {
+					let result_defaulted_params = generic_params.iter().filter_map(|param| match *param {
+						syn::GenericParam::Type(syn::TypeParam { ref ident, default: Some(ref default), .. }) => Some(quote!(#ident = #default)),
+						_ => None,
+					});
+
+					quote! {
+						#[doc = #result_doc_comment]
+						#error_kind_vis type #result_name <#(#result_params,)* #result_t_param #(, #result_defaulted_params)*> = ::std::result::Result<#generic_t, #error_name #ty_generics>;
+					}
+				}
 			});
 
 			quote! {
 				extern crate error_chain as #error_chain_name;
+				#tracing_error_extern_crate
+				#anyhow_extern_crate
 
 				impl #impl_generics #error_kind_name #ty_generics #where_clause {
 					/// A string describing the error kind.
-					pub fn description(&self) -> &str {
+					pub fn description(&self) -> #description_ty {
 						#[cfg_attr(feature = "cargo-clippy", allow(match_same_arms))]
 						match *self {
 							#(#error_kind_description_cases)*
 						}
 					}
+
+					/// The name of the error kind's variant.
+					pub fn kind_name(&self) -> &'static str {
+						match *self {
+							#(#error_kind_name_cases)*
+						}
+					}
+
+					#error_kind_code_fn
+
+					#error_kind_predicate_fns
 				}
 
 				impl #impl_generics ::std::fmt::Display for #error_kind_name #ty_generics #where_clause {
@@ -739,64 +2646,127 @@ This struct is made of three things:
 				}
 
 				#[doc = #error_doc_comment]
-				#[derive(Debug)]
-				#error_kind_vis struct #error_name #impl_generics (
+				#error_derive_debug
+				#error_must_use
+				#error_repr
+				#error_kind_vis struct #error_name #struct_generics (
 					/// The kind of the error.
-					pub #error_kind_name #ty_generics,
+					#error_field_vis #error_kind_name #ty_generics,
 
 					/// Contains the error chain and the backtrace.
-					pub #error_chain_name::State,
+					#error_field_vis #error_chain_name::State,
+
+					#location_field
+
+					#spantrace_field
+
+					#state_field
 				) #where_clause ;
 
 				#[allow(unused)]
 				impl #impl_generics #error_name #ty_generics #where_clause {
 					/// Constructs an error from a kind, and generates a backtrace.
+					#[cold]
+					#track_caller
 					pub fn from_kind(kind: #error_kind_name #ty_generics) -> Self {
-						#error_name(kind, #error_chain_name::State::default())
+						#error_name(kind, #default_state #extra_args)
+					}
+
+					/// Constructs an error from a kind, and generates a backtrace. This is the same as `from_kind()`.
+					#[cold]
+					#track_caller
+					pub fn new(kind: #error_kind_name #ty_generics) -> Self {
+						Self::from_kind(kind)
 					}
 
 					/// Constructs a chained error from another error and a kind, and generates a backtrace.
-					pub fn with_chain<__E, __K>(error: __E, kind: __K) -> Self
-						where __E: ::std::error::Error + Send + 'static, __K: Into<#error_kind_name #ty_generics>
+					#[cold]
+					#[inline(never)]
+					#track_caller
+					pub fn with_chain<#generic_e, #generic_k>(error: #generic_e, kind: #generic_k) -> Self
+						where #generic_e: ::std::error::Error + Send + 'static, #generic_k: Into<#error_kind_name #ty_generics>, Self: 'static
 					{
 						#error_name::with_boxed_chain(Box::new(error), kind)
 					}
 
 					/// Constructs a chained error from another boxed error and a kind, and generates a backtrace
-					pub fn with_boxed_chain<__K>(error: Box<::std::error::Error + Send>, kind: __K) -> #error_name #ty_generics
-						where __K: Into<#error_kind_name #ty_generics>
+					#[cold]
+					#[inline(never)]
+					#track_caller
+					pub fn with_boxed_chain<#generic_k>(error: Box<::std::error::Error + Send>, kind: #generic_k) -> #error_name #ty_generics
+						where #generic_k: Into<#error_kind_name #ty_generics>, Self: 'static
 					{
-						#error_name(kind.into(), #error_chain_name::State::new::<Self>(error))
+						#error_name(kind.into(), #chained_state_error #extra_args)
 					}
 
 					/// Returns the kind of the error.
 					pub fn kind(&self) -> &#error_kind_name #ty_generics { &self.0 }
 
 					/// Iterates over the error chain.
-					pub fn iter(&self) -> #error_chain_name::Iter {
+					pub fn iter(&self) -> #error_chain_name::Iter where Self: 'static {
 						#error_chain_name::ChainedError::iter(self)
 					}
 
-					/// Returns the backtrace associated with this error.
-					pub fn backtrace(&self) -> Option<&#error_chain_name::Backtrace> {
-						self.1.backtrace()
-					}
+					#source_chain_fns
+
+					#take_source_fns
+
+					#map_kind_fn
+
+					#const_constructor_fn
+
+					#sync_snapshot_fn
+
+					#backtrace_fn
+
+					#location_fn
+
+					#spantrace_fn
+
+					#state_fns
+
+					#anyhow_fn
+
+					#error_predicate_fns
+
+					#error_constructor_fns
+
+					#chain_iter_kinds_fn
+
+					#into_foreign_fn
 
 					/// Extends the error chain with a new entry.
-					pub fn chain_err<__F, __EK>(self, error: __F) -> Self where __F: FnOnce() -> __EK, __EK: Into<#error_kind_name #ty_generics> {
+					#track_caller
+					pub fn #chain_err_name<#generic_f, #generic_ek>(self, error: #generic_f) -> Self
+						where #generic_f: FnOnce() -> #generic_ek, #generic_ek: Into<#error_kind_name #ty_generics>, Self: 'static
+					{
 						#error_name::with_chain(self, Self::from_kind(error().into()))
 					}
 				}
 
+				#error_context_impl
+
 				impl #impl_generics ::std::error::Error for #error_name #ty_generics #where_clause {
-					fn description(&self) -> &str { self.0.description() }
+					#error_description_fn
+
+					fn cause(&self) -> Option<&::std::error::Error> {
+						#[cfg_attr(feature = "cargo-clippy", allow(match_same_arms))]
+						match self.1.next_error {
+							Some(ref c) => Some(&**c),
+							None => match self.0 {
+								#(#error_cause_cases)*
+
+								_ => None,
+							},
+						}
+					}
 
-					fn cause(&self) -> Option<&::std::error::Error> {
-						#[cfg_attr(feature = "cargo-clippy", allow(match_same_arms))]
+					fn source(&self) -> Option<&(::std::error::Error + 'static)> {
+						#[allow(unreachable_patterns)]
 						match self.1.next_error {
 							Some(ref c) => Some(&**c),
 							None => match self.0 {
-								#(#error_cause_cases)*
+								#(#error_source_cases)*
 
 								_ => None,
 							},
@@ -806,12 +2776,46 @@ This struct is made of three things:
 
 				impl #impl_generics ::std::fmt::Display for #error_name #ty_generics #where_clause {
 					fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
-						::std::fmt::Display::fmt(&self.0, f)
+						if !f.alternate() {
+							return ::std::fmt::Display::fmt(&self.0, f);
+						}
+
+						// `{:#}` also prints the rest of the chain. Each error is formatted without the alternate flag,
+						// so that one which prints its own chain for `{:#}`, like a foreign `Error`, doesn't repeat it.
+						write!(f, "{}", self.0)?;
+
+						let mut source = ::std::error::Error::source(self);
+						while let Some(err) = source {
+							write!(f, "{}{}", #chain_delimiter, err)?;
+							source = err.source();
+						}
+
+						Ok(())
 					}
 				}
 
 				#(#error_from_impls)*
 
+				#(#error_try_from_impls)*
+
+				#error_from_boxed_impl
+
+				#error_kind_into_boxed_impl
+
+				#from_unit_impls
+
+				#from_str_impl
+
+				#error_into_io_impl
+
+				#error_debug_impl
+
+				#anyhow_impls
+
+				#bail_macro
+
+				#run_fn
+
 				impl #impl_generics From<#error_kind_name #ty_generics> for #error_name #ty_generics #where_clause {
 					fn from(kind: #error_kind_name #ty_generics) -> Self { Self::from_kind(kind) }
 				}
@@ -822,19 +2826,22 @@ This struct is made of three things:
 					fn deref(&self) -> &Self::Target { &self.0 }
 				}
 
-				impl #impl_generics #error_chain_name::ChainedError for #error_name #ty_generics #where_clause {
+				impl #impl_generics #error_chain_name::ChainedError for #error_name #ty_generics #static_where_clause {
 					type ErrorKind = #error_kind_name #ty_generics;
 
+					#track_caller
 					fn new(kind: Self::ErrorKind, state: #error_chain_name::State) -> Self {
-						#error_name(kind, state)
+						#error_name(kind, state #extra_args)
 					}
 
+					#track_caller
 					fn from_kind(kind: Self::ErrorKind) -> Self {
 						Self::from_kind(kind)
 					}
 
-					fn with_chain<__E, __K>(error: __E, kind: __K) -> Self
-						where __E: ::std::error::Error + Send + 'static, __K: Into<Self::ErrorKind> {
+					#track_caller
+					fn with_chain<#generic_e, #generic_k>(error: #generic_e, kind: #generic_k) -> Self
+						where #generic_e: ::std::error::Error + Send + 'static, #generic_k: Into<Self::ErrorKind> {
 
 						Self::with_chain(error, kind)
 					}
@@ -851,46 +2858,54 @@ This struct is made of three things:
 						self.backtrace()
 					}
 
-					fn chain_err<__F, __EK>(self, error: __F) -> Self where __F: FnOnce() -> __EK, __EK: Into<Self::ErrorKind> {
-						self.chain_err(error)
+					#track_caller
+					fn chain_err<#generic_f, #generic_ek>(self, error: #generic_f) -> Self where #generic_f: FnOnce() -> #generic_ek, #generic_ek: Into<Self::ErrorKind> {
+						self.#chain_err_name(error)
 					}
 
 					#extract_backtrace_fn
 				}
 
-				/// Additional methods for `Result` and `Option`, for easy interaction with this crate.
-				#error_kind_vis trait #result_ext_name #result_ext_impl_generics_t #where_clause {
-					#[doc = #result_ext_chain_err_doc_comment]
-					fn chain_err<__F, __EK>(self, callback: __F) -> ::std::result::Result<__T, #error_name #ty_generics>
-						where __F: FnOnce() -> __EK, __EK: Into<#error_kind_name #ty_generics>;
-				}
+				#error_partial_eq_impl
 
-				impl #result_ext_impl_generics_t_e #result_ext_name #result_ext_ty_generics_t for ::std::result::Result<__T, __E> #where_clause {
-					fn chain_err<__F, __EK>(self, callback: __F) -> ::std::result::Result<__T, #error_name #ty_generics>
-						where __F: FnOnce() -> __EK, __EK: Into<#error_kind_name #ty_generics> {
-						self.map_err(move |e| {
-							let state = #error_chain_name::State::new::<#error_name #ty_generics>(Box::new(e));
-							#error_chain_name::ChainedError::new(callback().into(), state)
-						})
-					}
-				}
+				#error_eq_impl
 
-				impl #result_ext_impl_generics_t #result_ext_name #result_ext_ty_generics_t for ::std::option::Option<__T> #where_clause {
-					fn chain_err<__F, __EK>(self, callback: __F) -> ::std::result::Result<__T, #error_name #ty_generics>
-						where __F: FnOnce() -> __EK, __EK: Into<#error_kind_name #ty_generics> {
-						self.ok_or_else(move || {
-							#error_chain_name::ChainedError::from_kind(callback().into())
-						})
-					}
-				}
+				#error_hash_impl
+
+				#error_kind_ord_impl
+
+				#result_ext_trait
 
 				#result_wrapper
+
+				#prelude_mod
 			}
 		},
 
 		_ => panic!("#[derive(ErrorChain] can only be used with enums."),
 	};
 
+	let result = match module_name {
+		Some(module_name) => {
+			let module_doc_comment = format!("The error types generated for `{}`.", error_kind_name);
+
+			quote! {
+				#[doc = #module_doc_comment]
+				#module_macro_use
+				#module_vis mod #module_name {
+					#[allow(unused_imports)]
+					use super::*;
+
+					#error_kind_vis use super::#error_kind_name;
+
+					#result
+				}
+			}
+		},
+
+		None => result,
+	};
+
 	result.into()
 }
 
@@ -898,8 +2913,47 @@ struct TopLevelProperties {
 	error_kind_name: proc_macro2::Ident,
 	error_kind_vis: syn::Visibility,
 	error_name: proc_macro2::Ident,
-	result_ext_name: proc_macro2::Ident,
+	result_ext_name: Option<proc_macro2::Ident>,
 	result_name: Option<proc_macro2::Ident>,
+	result_error: Option<syn::Type>,
+	result_default: bool,
+	result_ok_default: bool,
+	error_partial_eq: bool,
+	error_eq: bool,
+	error_hash: bool,
+	error_doc: Option<String>,
+	result_doc: Option<String>,
+	result_ext_doc: Option<String>,
+	prelude_name: Option<proc_macro2::Ident>,
+	module_name: Option<proc_macro2::Ident>,
+	location: bool,
+	from_boxed: bool,
+	into_boxed: bool,
+	into_io: bool,
+	display: Option<syn::Expr>,
+	description: Option<syn::Expr>,
+	must_use: bool,
+	error_repr: Option<proc_macro2::TokenStream>,
+	error_debug: Option<syn::Expr>,
+	chain_delimiter: String,
+	from_unit: Option<proc_macro2::Ident>,
+	state: Option<syn::Type>,
+	opaque: bool,
+	ord: bool,
+	dynamic_description: bool,
+	no_error_description: bool,
+	spantrace: bool,
+	anyhow: bool,
+	no_from: bool,
+	predicates: bool,
+	debug_display: bool,
+	strict_msg: bool,
+	constructors: bool,
+	backtrace_env: Option<String>,
+	from_str: bool,
+	run: bool,
+	bail_name: Option<proc_macro2::Ident>,
+	chain_err_name: proc_macro2::Ident,
 	error_chain_name: proc_macro2::Ident,
 	support_backtrace: bool,
 }
@@ -907,8 +2961,47 @@ struct TopLevelProperties {
 impl<'a> From<&'a syn::DeriveInput> for TopLevelProperties {
 	fn from(ast: &'a syn::DeriveInput) -> Self {
 		let mut error_name = proc_macro2::Ident::new("Error", proc_macro2::Span::call_site());
-		let mut result_ext_name = proc_macro2::Ident::new("ResultExt", proc_macro2::Span::call_site());
+		let mut result_ext_name = Some(proc_macro2::Ident::new("ResultExt", proc_macro2::Span::call_site()));
 		let mut result_name = Some(proc_macro2::Ident::new("Result", proc_macro2::Span::call_site()));
+		let mut result_error = None;
+		let mut result_default = false;
+		let mut result_ok_default = false;
+		let mut error_partial_eq = false;
+		let mut error_eq = false;
+		let mut error_hash = false;
+		let mut error_doc = None;
+		let mut result_doc = None;
+		let mut result_ext_doc = None;
+		let mut prelude_name = None;
+		let mut module_name = None;
+		let mut location = false;
+		let mut from_boxed = false;
+		let mut into_boxed = false;
+		let mut into_io = false;
+		let mut display = None;
+		let mut description = None;
+		let mut must_use = false;
+		let mut error_repr = None;
+		let mut error_debug = None;
+		let mut chain_delimiter = ": caused by: ".to_string();
+		let mut from_unit = None;
+		let mut state = None;
+		let mut opaque = false;
+		let mut ord = false;
+		let mut dynamic_description = false;
+		let mut no_error_description = false;
+		let mut spantrace = false;
+		let mut anyhow = false;
+		let mut no_from = false;
+		let mut predicates = false;
+		let mut debug_display = false;
+		let mut strict_msg = false;
+		let mut constructors = false;
+		let mut backtrace_env = None;
+		let mut from_str = false;
+		let mut run = false;
+		let mut bail_name = None;
+		let mut chain_err_name = proc_macro2::Ident::new("chain_err", proc_macro2::Span::call_site());
 		let mut support_backtrace = true;
 
 		for attr in &ast.attrs {
@@ -927,8 +3020,14 @@ impl<'a> From<&'a syn::DeriveInput> for TopLevelProperties {
 									"error" => error_name = syn::parse_str(value).unwrap_or_else(|err|
 										panic!("Could not parse `error` value as an identifier - {}", err)),
 
-									"result_ext" => result_ext_name = syn::parse_str(value).unwrap_or_else(|err|
-										panic!("Could not parse `result_ext` value as an identifier - {}", err)),
+									"result_ext" => result_ext_name =
+										if value == "" {
+											None
+										}
+										else {
+											Some(syn::parse_str(value).unwrap_or_else(|err|
+												panic!("Could not parse `result_ext` value as an identifier - {}", err)))
+										},
 
 									"result" => result_name =
 										if value == "" {
@@ -939,11 +3038,60 @@ impl<'a> From<&'a syn::DeriveInput> for TopLevelProperties {
 												panic!("Could not parse `result` value as an identifier - {}", err)))
 										},
 
+									"result_error" => result_error = Some(syn::parse_str(value).unwrap_or_else(|err|
+										panic!("Could not parse `result_error` value as a type - {}", err))),
+
+									"error_doc" => error_doc = Some(value.to_string()),
+
+									"result_doc" => result_doc = Some(value.to_string()),
+
+									"result_ext_doc" => result_ext_doc = Some(value.to_string()),
+
+									"prelude" => prelude_name = Some(syn::parse_str(value).unwrap_or_else(|err|
+										panic!("Could not parse `prelude` value as an identifier - {}", err))),
+
+									"module" => module_name = Some(syn::parse_str(value).unwrap_or_else(|err|
+										panic!("Could not parse `module` value as an identifier - {}", err))),
+
+									"display" => display = Some(syn::parse_str(value).unwrap_or_else(|err|
+										panic!("Could not parse `display` value as an expression - {}", err))),
+
+									"description" => description = Some(syn::parse_str(value).unwrap_or_else(|err|
+										panic!("Could not parse `description` value as an expression - {}", err))),
+
+									"bail" => bail_name = Some(syn::parse_str(value).unwrap_or_else(|err|
+										panic!("Could not parse `bail` value as an identifier - {}", err))),
+
+									"chain_err" => chain_err_name = syn::parse_str(value).unwrap_or_else(|err|
+										panic!("Could not parse `chain_err` value as an identifier - {}", err)),
+
 									"backtrace" => support_backtrace = value.parse().unwrap_or_else(|err|
 										panic!("Could not parse `backtrace` value - {}", err)),
 
+									"error_repr" => {
+										if value == "transparent" {
+											panic!("`error_repr` cannot be `transparent` because the generated error has more than one non-zero-sized field.");
+										}
+
+										error_repr = Some(syn::parse_str(value).unwrap_or_else(|err|
+											panic!("Could not parse `error_repr` value - {}", err)));
+									},
+
+									"error_debug" => error_debug = Some(syn::parse_str(value).unwrap_or_else(|err|
+										panic!("Could not parse `error_debug` value as an expression - {}", err))),
+
+									"chain_delimiter" => chain_delimiter = value.to_string(),
+
+									"from_unit" => from_unit = Some(syn::parse_str(value).unwrap_or_else(|err|
+										panic!("Could not parse `from_unit` value as an identifier - {}", err))),
+
+									"state" => state = Some(syn::parse_str(value).unwrap_or_else(|err|
+										panic!("Could not parse `state` value as a type - {}", err))),
+
+									"backtrace_env" => backtrace_env = Some(value.to_string()),
+
 									_ =>
-										panic!("Could not parse `error_chain` attribute - expected one of `error`, `result_ext`, `result`, `backtrace` but got {}", ident),
+										panic!("Could not parse `error_chain` attribute - expected one of `error`, `result_ext`, `result`, `result_error`, `result_default`, `result_ok_default`, `error_partial_eq`, `error_eq`, `error_hash`, `error_doc`, `result_doc`, `result_ext_doc`, `prelude`, `location`, `from_boxed`, `into_boxed`, `into_io`, `display`, `description`, `must_use`, `ord`, `dynamic_description`, `no_error_description`, `spantrace`, `anyhow`, `no_from`, `predicates`, `run`, `bail`, `chain_err`, `backtrace`, `error_repr`, `error_debug`, `chain_delimiter`, `from_unit`, `opaque`, `debug_display`, `state`, `strict_msg`, `constructors`, `backtrace_env`, `from_str`, `module` but got {}", ident),
 								}
 							},
 
@@ -951,12 +3099,78 @@ impl<'a> From<&'a syn::DeriveInput> for TopLevelProperties {
 								syn::MetaNameValue { ref ident, lit: syn::Lit::Bool(syn::LitBool { value, .. }), .. }))
 								if ident == "backtrace" => support_backtrace = value,
 
-							_ => panic!("Could not parse `error_chain` attribute - expected one of `error`, `result_ext`, `result`, `backtrace`"),
+							syn::NestedMeta::Meta(syn::Meta::List(syn::MetaList { ref ident, ref nested, .. })) if ident == "backtrace" => {
+								let mut nested = nested.iter();
+								support_backtrace = match (nested.next(), nested.next()) {
+									// `true` and `false` are parsed as words rather than literals
+									(Some(&syn::NestedMeta::Meta(syn::Meta::Word(ref value))), None) => value.to_string().parse().unwrap_or_else(|err|
+										panic!("Could not parse `backtrace` value - {}", err)),
+
+									_ => panic!("Could not parse `backtrace` value - expected `backtrace(true)` or `backtrace(false)`"),
+								};
+							},
+
+							syn::NestedMeta::Meta(syn::Meta::Word(ident)) => match &*ident.to_string() {
+								"result_default" => result_default = true,
+
+								"result_ok_default" => result_ok_default = true,
+
+								"error_partial_eq" => error_partial_eq = true,
+
+								"error_eq" => error_eq = true,
+
+								"error_hash" => error_hash = true,
+
+								"location" => location = true,
+
+								"from_boxed" => from_boxed = true,
+
+								"into_boxed" => into_boxed = true,
+
+								"into_io" => into_io = true,
+
+								"must_use" => must_use = true,
+
+								"ord" => ord = true,
+
+								"dynamic_description" => dynamic_description = true,
+
+								"no_error_description" => no_error_description = true,
+
+								"spantrace" if cfg!(feature = "tracing-error") => spantrace = true,
+
+								"spantrace" => panic!("The `spantrace` attribute requires the `tracing-error` feature of derive-error-chain to be enabled"),
+
+								"anyhow" if cfg!(feature = "anyhow") => anyhow = true,
+
+								"anyhow" => panic!("The `anyhow` attribute requires the `anyhow` feature of derive-error-chain to be enabled"),
+
+								"no_from" => no_from = true,
+
+								"predicates" => predicates = true,
+
+								"run" => run = true,
+
+								"opaque" => opaque = true,
+
+								"debug_display" => debug_display = true,
+
+								"strict_msg" => strict_msg = true,
+
+								"constructors" => constructors = true,
+
+								"from_str" => from_str = true,
+
+								_ =>
+									panic!("Could not parse `error_chain` attribute - expected one of `result_default`, `result_ok_default`, `error_partial_eq`, `error_eq`, `error_hash`, `location`, `from_boxed`, `into_boxed`, `into_io`, `must_use`, `ord`, `dynamic_description`, `no_error_description`, `spantrace`, `anyhow`, `no_from`, `predicates`, `run`, `opaque`, `debug_display`, `strict_msg`, `constructors`, `from_str` but got {}", ident),
+							},
+
+							_ => panic!("Could not parse `error_chain` attribute - expected one of `error`, `result_ext`, `result`, `result_error`, `result_default`, `result_ok_default`, `error_partial_eq`, `error_eq`, `error_hash`, `error_doc`, `result_doc`, `result_ext_doc`, `prelude`, `location`, `from_boxed`, `into_boxed`, `into_io`, `display`, `description`, `must_use`, `ord`, `dynamic_description`, `no_error_description`, `spantrace`, `anyhow`, `no_from`, `predicates`, `run`, `bail`, `chain_err`, `backtrace`, `error_repr`, `error_debug`, `chain_delimiter`, `from_unit`, `opaque`, `debug_display`, `state`, `strict_msg`, `constructors`, `backtrace_env`, `from_str`, `module`"),
 						}
 					}
 				},
 
-				_ => panic!("Could not parse `error_chain` attribute - expected one of `error`, `result_ext`, `result`, `backtrace`"),
+				_ => panic!("Could not parse `error_chain` attribute - expected one of `error`, `result_ext`, `result`, `result_error`, `result_default`, `result_ok_default`, `error_partial_eq`, `error_eq`, `error_hash`, `error_doc`, `result_doc`, `result_ext_doc`, `prelude`, `location`, `from_boxed`, `into_boxed`, `into_io`, `display`, `description`, `must_use`, `ord`, `dynamic_description`, `no_error_description`, `spantrace`, `anyhow`, `no_from`, `predicates`, `run`, `bail`, `chain_err`, `backtrace`, `error_repr`, `error_debug`, `chain_delimiter`, `from_unit`, `opaque`, `debug_display`, `state`, `strict_msg`, `constructors`, `backtrace_env`, `from_str`, `module`"),
 			}
 		}
 
@@ -969,6 +3183,45 @@ impl<'a> From<&'a syn::DeriveInput> for TopLevelProperties {
 			error_name,
 			result_ext_name,
 			result_name,
+			result_error,
+			result_default,
+			result_ok_default,
+			error_partial_eq,
+			error_eq,
+			error_hash,
+			error_doc,
+			result_doc,
+			result_ext_doc,
+			prelude_name,
+			module_name,
+			location,
+			from_boxed,
+			into_boxed,
+			into_io,
+			display,
+			description,
+			must_use,
+			error_repr,
+			error_debug,
+			chain_delimiter,
+			from_unit,
+			state,
+			opaque,
+			ord,
+			dynamic_description,
+			no_error_description,
+			spantrace,
+			anyhow,
+			no_from,
+			predicates,
+			debug_display,
+			strict_msg,
+			constructors,
+			backtrace_env,
+			from_str,
+			run,
+			bail_name,
+			chain_err_name,
 			error_chain_name,
 			support_backtrace,
 		}
@@ -982,6 +3235,12 @@ struct Link {
 	custom_description: Option<CustomFormatter>,
 	custom_display: Option<CustomFormatter>,
 	custom_cause: Option<syn::Expr>,
+	source_field: Option<usize>,
+	from_field: bool,
+	force_from: bool,
+	extract: bool,
+	discriminant: Option<syn::Expr>,
+	deprecated: bool,
 }
 
 enum LinkType {
@@ -992,7 +3251,11 @@ enum LinkType {
 }
 
 impl From<syn::Variant> for Link {
-	fn from(syn::Variant { ident: variant_ident, attrs, fields: variant_fields, .. }: syn::Variant) -> Self {
+	fn from(syn::Variant { ident: variant_ident, attrs, fields: variant_fields, discriminant }: syn::Variant) -> Self {
+		let discriminant = discriminant.map(|(_, discriminant)| discriminant);
+
+		let deprecated = attrs.iter().any(|attr| !attr.path.global() && attr.path.segments.len() == 1 && attr.path.segments[0].ident == "deprecated");
+
 		let is_msg = loop {
 			if variant_ident != "Msg" {
 				break false;
@@ -1001,14 +3264,14 @@ impl From<syn::Variant> for Link {
 			if let syn::Fields::Unnamed(syn::FieldsUnnamed { ref unnamed, .. }) = variant_fields {
 				if unnamed.len() == 1 {
 					if let syn::Type::Path(syn::TypePath { ref path, .. }) = unnamed[0].ty {
-						if !path.global() && path.segments.len() == 1 && path.segments[0].ident == "String" {
+						if !path.global() && path.segments.len() == 1 && (path.segments[0].ident == "String" || is_box_str(&path.segments[0])) {
 							break true;
 						}
 					}
 				}
 			}
 
-			panic!("Expected Msg member to be a tuple of String");
+			panic!("Expected Msg member to be a tuple of String or Box<str>");
 		};
 
 		if is_msg {
@@ -1019,13 +3282,22 @@ impl From<syn::Variant> for Link {
 				custom_description: None,
 				custom_display: None,
 				custom_cause: None,
+				source_field: None,
+				from_field: false,
+				force_from: false,
+				extract: false,
+				discriminant,
+				deprecated,
 			};
 		}
 
-		let mut link_type = None;
+		let mut link_types = vec![];
 		let mut custom_description = None;
 		let mut custom_display = None;
 		let mut custom_cause: Option<syn::Expr> = None;
+		let mut custom_context = None;
+		let mut source_field_index = None;
+		let mut words = vec![];
 
 		for attr in attrs {
 			if !is_error_chain_attribute(&attr) {
@@ -1035,20 +3307,7 @@ impl From<syn::Variant> for Link {
 			if let Some(syn::Meta::List(syn::MetaList { nested, .. })) = attr.interpret_meta() {
 				for nested_meta in nested {
 					match nested_meta {
-						syn::NestedMeta::Meta(syn::Meta::Word(ident)) => match &*ident.to_string() {
-							"foreign" => match variant_fields {
-								syn::Fields::Unnamed(syn::FieldsUnnamed { ref unnamed, .. }) if unnamed.len() == 1 =>
-									link_type = Some(LinkType::Foreign(unnamed[0].ty.clone())),
-
-								_ => panic!("Foreign link {} must be a tuple of one element (the foreign error type).", variant_ident),
-							},
-
-							"custom" => link_type = Some(LinkType::Custom),
-
-							_ => panic!(
-								"Could not parse `error_chain` attribute of member {} - expected one of `foreign`, `custom` but got {}",
-								variant_ident, ident),
-						},
+						syn::NestedMeta::Meta(syn::Meta::Word(ident)) => words.push(ident.to_string()),
 
 						syn::NestedMeta::Meta(syn::Meta::NameValue(syn::MetaNameValue { ident, lit: syn::Lit::Str(value), .. })) => {
 							let value = &value.value();
@@ -1056,10 +3315,10 @@ impl From<syn::Variant> for Link {
 							match &*ident.to_string() {
 								"link" => match variant_fields {
 									syn::Fields::Unnamed(syn::FieldsUnnamed { ref unnamed, .. }) if unnamed.len() == 1 =>
-										link_type = Some(LinkType::Chainable(
+										add_link_type(&mut link_types, LinkType::Chainable(
 											syn::parse_str(value).unwrap_or_else(|err|
 												panic!("Could not parse `link` attribute of member {} as a type - {}", variant_ident, err)),
-											unnamed[0].ty.clone())),
+											unnamed[0].ty.clone()), format!("`link = \"{}\"`", value)),
 
 									_ => panic!("Chainable link {} must be a tuple of one element (the chainable error kind).", variant_ident),
 								},
@@ -1073,12 +3332,17 @@ impl From<syn::Variant> for Link {
 								"cause" => custom_cause = Some(syn::parse_str(value).unwrap_or_else(|err|
 									panic!("Could not parse `cause` attribute of member {} as an expression - {}", variant_ident, err))),
 
+								"context" => custom_context = Some(CustomFormatter::from_format_string(value.to_string(), "context", &variant_ident, &variant_fields)),
+
 								_ => panic!(
-									"Could not parse `error_chain` attribute of member {} - expected one of `link`, `description`, `display`, `cause` but got {}",
+									"Could not parse `error_chain` attribute of member {} - expected one of `link`, `description`, `display`, `cause`, `context`, `source_field` but got {}",
 									variant_ident, ident),
 							}
 						},
 
+						syn::NestedMeta::Meta(syn::Meta::NameValue(syn::MetaNameValue { ref ident, lit: syn::Lit::Int(ref value), .. })) if ident == "source_field" =>
+							source_field_index = Some(value.value()),
+
 						_ => panic!("Could not parse `error_chain` attribute of member {} - expected term or name-value meta item", variant_ident),
 					}
 				}
@@ -1100,31 +3364,53 @@ impl From<syn::Variant> for Link {
 					tt.into_iter()
 				};
 
-				let ident = match tts.next() {
-					Some(proc_macro2::TokenTree::Ident(ident)) => ident,
-					Some(tt) => panic!("Could not parse `error_chain` attribute of member {} - expected a term but got {}", variant_ident, tt),
-					None => panic!("Could not parse `error_chain` attribute of member {} - expected a term", variant_ident),
+				// Leading terms like `custom` can be followed by a single `name = value`. The value is not split at commas
+				// since it may contain some at the top level, such as in the parameter list of a closure.
+				let ident = loop {
+					let ident = match tts.next() {
+						Some(proc_macro2::TokenTree::Ident(ident)) => ident,
+						Some(tt) => panic!("Could not parse `error_chain` attribute of member {} - expected a term but got {}", variant_ident, tt),
+
+						// Empty attribute or trailing comma
+						None => break None,
+					};
+					let ident = ident.to_string();
+
+					match tts.next() {
+						Some(proc_macro2::TokenTree::Punct(ref punct)) if punct.as_char() == '=' => break Some(ident),
+						Some(proc_macro2::TokenTree::Punct(ref punct)) if punct.as_char() == ',' => words.push(ident),
+						Some(tt) => panic!("Could not parse `error_chain` attribute of member {} - expected `=` or `,` but got {}", variant_ident, tt),
+						None => {
+							words.push(ident);
+							break None;
+						},
+					}
 				};
-				let ident = ident.to_string();
 
-				match tts.next() {
-					Some(proc_macro2::TokenTree::Punct(ref punct)) if punct.as_char() == '=' => (),
-					Some(tt) => panic!("Could not parse `error_chain` attribute of member {} - expected `=` but got {}", variant_ident, tt),
-					None => panic!("Could not parse `error_chain` attribute of member {} - expected `=`", variant_ident),
-				}
+				let ident = match ident {
+					Some(ident) => ident,
+					None => continue,
+				};
 
-				let value: proc_macro2::TokenStream = tts.collect();
+				let mut value: Vec<_> = tts.collect();
+				match value.last() {
+					Some(proc_macro2::TokenTree::Punct(ref punct)) if punct.as_char() == ',' => { value.pop(); },
+					_ => (),
+				}
+				let value: proc_macro2::TokenStream = value.into_iter().collect();
 				if value.is_empty() {
 					panic!("Could not parse `error_chain` attribute of member {} - expected tokens after `=`", variant_ident);
 				}
 
 				match &*ident {
 					"link" => match variant_fields {
-						syn::Fields::Unnamed(syn::FieldsUnnamed { ref unnamed, .. }) if unnamed.len() == 1 =>
-							link_type = Some(LinkType::Chainable(
+						syn::Fields::Unnamed(syn::FieldsUnnamed { ref unnamed, .. }) if unnamed.len() == 1 => {
+							let declaration = format!("`link = {}`", value);
+							add_link_type(&mut link_types, LinkType::Chainable(
 								syn::parse2(value).unwrap_or_else(|err|
 									panic!("Could not parse `link` attribute of member {} as a type - {}", variant_ident, err)),
-								unnamed[0].ty.clone())),
+								unnamed[0].ty.clone()), declaration);
+						},
 
 						_ => panic!("Chainable link {} must be a tuple of one element (the chainable error kind).", variant_ident),
 					},
@@ -1136,16 +3422,160 @@ impl From<syn::Variant> for Link {
 					"cause" => custom_cause = Some(syn::parse2(value).unwrap_or_else(|err|
 						panic!("Could not parse `cause` attribute of member {} as an expression - {}", variant_ident, err))),
 
+					"context" => custom_context = Some(CustomFormatter::parse(value, "context", &variant_ident, &variant_fields)),
+
+					"source_field" => source_field_index = Some(syn::parse2::<syn::LitInt>(value).unwrap_or_else(|err|
+						panic!("Could not parse `source_field` attribute of member {} as an integer - {}", variant_ident, err)).value()),
+
 					_ => panic!(
-						"Could not parse `error_chain` attribute of member {} - expected one of `link`, `description`, `display`, `cause` but got {}",
+						"Could not parse `error_chain` attribute of member {} - expected one of `link`, `description`, `display`, `cause`, `context`, `source_field` but got {}",
 						variant_ident, ident),
 				}
 			}
 		}
 
-		let link_type = link_type.unwrap_or_else(||
+		let mut force_from = false;
+		let mut extract = false;
+
+		for word in words {
+			match &*word {
+				"force_from" => force_from = true,
+				"extract" => extract = true,
+				_ => add_link_type(&mut link_types, link_type_from_word(&word, &variant_ident, &variant_fields), format!("`{}`", word)),
+			}
+		}
+
+		if link_types.len() > 1 {
+			// Sorted so that the message doesn't depend on the order the declarations were specified in
+			let mut declarations: Vec<_> = link_types.iter().map(|link_type| &*link_type.1).collect();
+			declarations.sort();
+			let (last, rest) = declarations.split_last().unwrap();
+			panic!("Member {} has conflicting link types {} and {}.", variant_ident, rest.join(", "), last);
+		}
+
+		let (link_type, _) = link_types.pop().unwrap_or_else(||
 			panic!(r#"Member {} does not have any of #[error_chain(link = "...")] or #[error_chain(foreign)] or #[error_chain(custom)]."#, variant_ident));
 
+		let mut source_field = None;
+		let mut from_field = None;
+		let mut has_default_fields = false;
+
+		for (i, field) in variant_fields.iter().enumerate() {
+			for attr in &field.attrs {
+				if is_from_field_attribute(attr) {
+					if !attr.tts.is_empty() {
+						panic!("Could not parse `from` attribute of a field of member {} - expected no arguments", variant_ident);
+					}
+
+					if from_field.is_some() {
+						panic!("Member {} has more than one `#[from]` field.", variant_ident);
+					}
+
+					from_field = Some(i);
+					continue;
+				}
+
+				if !is_error_chain_attribute(attr) {
+					continue;
+				}
+
+				let word = match attr.interpret_meta() {
+					Some(syn::Meta::List(syn::MetaList { ref nested, .. })) if nested.len() == 1 => match nested[0] {
+						syn::NestedMeta::Meta(syn::Meta::Word(ref ident)) if ident == "source" || ident == "default" => ident.to_string(),
+						_ => panic!("Could not parse `error_chain` attribute of a field of member {} - expected one of `source`, `default`", variant_ident),
+					},
+
+					_ => panic!("Could not parse `error_chain` attribute of a field of member {} - expected one of `source`, `default`", variant_ident),
+				};
+
+				if word == "default" {
+					has_default_fields = true;
+					continue;
+				}
+
+				if source_field.is_some() {
+					panic!("Member {} has more than one `source` field.", variant_ident);
+				}
+
+				source_field = Some(i);
+			}
+		}
+
+		if let Some(source_field_index) = source_field_index {
+			if source_field.is_some() {
+				panic!("Member {} cannot have both a `source_field` attribute and a `source` field.", variant_ident);
+			}
+
+			let num_fields = variant_fields.iter().count();
+			if source_field_index >= num_fields as u64 {
+				panic!("`source_field` attribute of member {} is {} but the member only has {} field(s).", variant_ident, source_field_index, num_fields);
+			}
+
+			source_field = Some(source_field_index as usize);
+		}
+
+		if let Some(from_field) = from_field {
+			match link_type {
+				LinkType::Custom => (),
+				_ => panic!("Member {} has a `#[from]` field but is not a custom link.", variant_ident),
+			}
+
+			if foreign_field_index(&variant_fields) != Some(from_field) {
+				panic!("Member {} has a `#[from]` field, so all its other fields must be marked with `#[error_chain(default)]`.", variant_ident);
+			}
+
+			// Like with thiserror, the field is also the source of the error unless another one was specified
+			if source_field.is_none() && custom_cause.is_none() {
+				source_field = Some(from_field);
+			}
+		}
+
+		if force_from {
+			match link_type {
+				LinkType::Foreign(_) => (),
+				_ => panic!("Member {} has a `force_from` attribute but is not a foreign link.", variant_ident),
+			}
+		}
+
+		if extract {
+			match link_type {
+				LinkType::Foreign(_) | LinkType::Custom => (),
+				_ => panic!("Member {} has an `extract` attribute but is not a foreign or custom link.", variant_ident),
+			}
+		}
+
+		if has_default_fields {
+			match link_type {
+				LinkType::Foreign(_) => (),
+				LinkType::Custom if from_field.is_some() => (),
+				_ => panic!("Member {} has a `default` field but is not a foreign link or a custom link with a `#[from]` field.", variant_ident),
+			}
+		}
+
+		if source_field.is_some() {
+			match link_type {
+				LinkType::Custom => (),
+				_ => panic!("Member {} has a `source` field but is not a custom link.", variant_ident),
+			}
+
+			if custom_cause.is_some() {
+				panic!("Member {} cannot have both a `cause` attribute and a `source` field.", variant_ident);
+			}
+		}
+
+		if let Some(custom_context) = custom_context {
+			match link_type {
+				LinkType::Foreign(_) => (),
+				_ => panic!("Member {} has a `context` attribute but is not a foreign link.", variant_ident),
+			}
+
+			if custom_display.is_some() {
+				panic!("Member {} cannot have both `display` and `context` attributes.", variant_ident);
+			}
+
+			custom_display = Some(custom_context);
+		}
+
 		Link {
 			variant_ident,
 			variant_fields,
@@ -1153,124 +3583,288 @@ impl From<syn::Variant> for Link {
 			custom_description,
 			custom_display,
 			custom_cause,
+			source_field,
+			from_field: from_field.is_some(),
+			force_from,
+			extract,
+			discriminant,
+			deprecated,
 		}
 	}
 }
 
 impl Link {
-	fn error_kind_description(&self, error_kind_name: &proc_macro2::Ident) -> proc_macro2::TokenStream {
+	// Deprecated variants are still matched on and constructed by the generated code, which shouldn't warn. The lint is only allowed
+	// on the match arms and items that mention such a variant, instead of on whole impls that also contain the user's closures.
+	fn allow_deprecated(&self) -> Option<proc_macro2::TokenStream> {
+		if self.deprecated {
+			Some(quote!(#[allow(deprecated)]))
+		}
+		else {
+			None
+		}
+	}
+
+	// `Box<dyn Error>` doesn't implement `Error` itself, and neither do `Arc<T>` and `Rc<T>` before Rust 1.52,
+	// so a foreign error held in one of these is used through the pointer
+	fn foreign_error_ref(&self) -> proc_macro2::TokenStream {
+		if let LinkType::Foreign(syn::Type::Path(syn::TypePath { ref path, .. })) = self.link_type {
+			if let Some(segment) = path.segments.iter().last() {
+				if segment.ident == "Box" || segment.ident == "Arc" || segment.ident == "Rc" {
+					return quote!(&**err);
+				}
+			}
+		}
+
+		quote!(err)
+	}
+
+	fn chainable_error_ty(&self) -> Option<&syn::Type> {
+		if let LinkType::Chainable(ref error_ty, _) = self.link_type {
+			Some(error_ty)
+		}
+		else {
+			None
+		}
+	}
+
+	fn foreign_error_ty(&self) -> Option<&syn::Type> {
+		if let LinkType::Foreign(ref error_ty) = self.link_type {
+			Some(error_ty)
+		}
+		else {
+			None
+		}
+	}
+
+	fn error_kind_description(
+		&self,
+		error_kind_name: &proc_macro2::Ident,
+		fallback_description: Option<&syn::Expr>,
+		dynamic_description: bool,
+	) -> proc_macro2::TokenStream {
+		let allow_deprecated = self.allow_deprecated();
+
 		let variant_ident = &self.variant_ident;
 
-		match (self.custom_description.as_ref(), &self.link_type) {
-			(_, &LinkType::Msg) => quote! {
-				#error_kind_name::#variant_ident(ref s) => s,
-			},
+		let (pattern, description) = match (self.custom_description.as_ref(), &self.link_type) {
+			(_, &LinkType::Msg) => (quote!((ref s)), quote!(s)),
 
 			(Some(&CustomFormatter::FormatString { ref format_string, .. }), &LinkType::Chainable(_, _)) |
-			(Some(&CustomFormatter::FormatString { ref format_string, .. }), &LinkType::Foreign(_)) => quote! {
-				#error_kind_name::#variant_ident(_) => #format_string,
-			},
+			(Some(&CustomFormatter::FormatString { ref format_string, .. }), &LinkType::Foreign(_)) =>
+				(fields_pattern_ignore(&self.variant_fields), quote!(#format_string)),
 
 			(Some(&CustomFormatter::Expr(ref custom_description)), &LinkType::Chainable(_, _)) |
-			(Some(&CustomFormatter::Expr(ref custom_description)), &LinkType::Foreign(_)) if is_closure(custom_description) => quote! {
-				#error_kind_name::#variant_ident(ref err) => {
-					#[cfg_attr(feature = "cargo-clippy", allow(redundant_closure_call))]
-					{ (#custom_description)(err) }
-				},
-			},
+			(Some(&CustomFormatter::Expr(ref custom_description)), &LinkType::Foreign(_)) if is_closure(custom_description) => (
+				single_field_pattern(&self.variant_fields, quote!(ref err)),
+				call_closure(custom_description, quote!(err), 1),
+			),
 
 			(Some(&CustomFormatter::Expr(ref custom_description)), &LinkType::Chainable(_, _)) |
-			(Some(&CustomFormatter::Expr(ref custom_description)), &LinkType::Foreign(_)) => quote! {
-				#error_kind_name::#variant_ident(ref err) => #custom_description(err),
-			},
-
-			(Some(&CustomFormatter::FormatString { ref format_string, .. }), &LinkType::Custom) => {
-				let pattern = fields_pattern_ignore(&self.variant_fields);
+			(Some(&CustomFormatter::Expr(ref custom_description)), &LinkType::Foreign(_)) =>
+				(single_field_pattern(&self.variant_fields, quote!(ref err)), quote!(#custom_description(err))),
 
-				quote! {
-					#error_kind_name::#variant_ident #pattern => #format_string,
-				}
-			},
+			(Some(&CustomFormatter::FormatString { ref format_string, .. }), &LinkType::Custom) =>
+				(fields_pattern_ignore(&self.variant_fields), quote!(#format_string)),
 
 			(Some(&CustomFormatter::Expr(ref custom_description)), &LinkType::Custom) => {
 				let pattern = fields_pattern(&self.variant_fields);
 				let args = args(&self.variant_fields);
 
 				if is_closure(custom_description) {
-					quote! {
-						#error_kind_name::#variant_ident #pattern => {
-							#[cfg_attr(feature = "cargo-clippy", allow(redundant_closure_call))]
-							{ (#custom_description)(#args) }
-						},
-					}
+					(pattern, call_closure(custom_description, args, self.variant_fields.iter().count()))
 				}
 				else {
-					quote! {
-						#error_kind_name::#variant_ident #pattern => #custom_description(#args),
-					}
+					(pattern, quote!(#custom_description(#args)))
+				}
+			},
+
+			(None, _) if fallback_description.is_some() => {
+				let fallback_description = fallback_description.unwrap();
+
+				if is_closure(fallback_description) {
+					(fields_pattern_ignore(&self.variant_fields), call_closure(fallback_description, quote!(self), 1))
+				}
+				else {
+					(fields_pattern_ignore(&self.variant_fields), quote!(#fallback_description(self)))
 				}
 			},
 
-			(None, &LinkType::Chainable(_, _)) => quote! {
-				#error_kind_name::#variant_ident(ref kind) => kind.description(),
-			},
+			(None, &LinkType::Chainable(_, _)) => (quote!((ref kind)), quote!(kind.description())),
+
+			(None, &LinkType::Foreign(_)) => {
+				let err = self.foreign_error_ref();
+				(single_field_pattern(&self.variant_fields, quote!(ref err)), quote!(::std::error::Error::description(#err)))
+			},
+
+			(None, &LinkType::Custom) => {
+				let name = unraw_name(variant_ident);
+				(fields_pattern_ignore(&self.variant_fields), quote!(#name))
+			},
+		};
+
+		if dynamic_description {
+			quote! {
+				#allow_deprecated
+				#error_kind_name::#variant_ident #pattern => ::std::borrow::Cow::from(#description),
+			}
+		}
+		else {
+			quote! {
+				#allow_deprecated
+				#error_kind_name::#variant_ident #pattern => #description,
+			}
+		}
+	}
+
+	fn error_kind_name_case(&self, error_kind_name: &proc_macro2::Ident) -> proc_macro2::TokenStream {
+		let allow_deprecated = self.allow_deprecated();
+
+		let variant_ident = &self.variant_ident;
+		let pattern = fields_pattern_ignore(&self.variant_fields);
+		let name = unraw_name(variant_ident);
+
+		quote! {
+			#allow_deprecated
+			#error_kind_name::#variant_ident #pattern => #name,
+		}
+	}
+
+	fn error_kind_code_case(&self, error_kind_name: &proc_macro2::Ident) -> proc_macro2::TokenStream {
+		let allow_deprecated = self.allow_deprecated();
+
+		let variant_ident = &self.variant_ident;
+		let pattern = fields_pattern_ignore(&self.variant_fields);
+
+		match self.discriminant {
+			Some(ref discriminant) => quote! {
+				#allow_deprecated
+				#error_kind_name::#variant_ident #pattern => Some((#discriminant) as i64),
+			},
+
+			None => quote! {
+				#allow_deprecated
+				#error_kind_name::#variant_ident #pattern => None,
+			},
+		}
+	}
+
+	fn error_kind_index_case(&self, error_kind_name: &proc_macro2::Ident, index: usize) -> proc_macro2::TokenStream {
+		let allow_deprecated = self.allow_deprecated();
+
+		let variant_ident = &self.variant_ident;
+		let pattern = fields_pattern_ignore(&self.variant_fields);
+		let index = proc_macro2::Literal::usize_unsuffixed(index);
+
+		quote! {
+			#allow_deprecated
+			#error_kind_name::#variant_ident #pattern => #index,
+		}
+	}
+
+	fn error_kind_cmp_case(&self, error_kind_name: &proc_macro2::Ident) -> proc_macro2::TokenStream {
+		let allow_deprecated = self.allow_deprecated();
+
+		let variant_ident = &self.variant_ident;
 
-			(None, &LinkType::Foreign(_)) => quote! {
-				#error_kind_name::#variant_ident(ref err) => ::std::error::Error::description(err),
+		let fields: Vec<_> = self.variant_fields.iter().collect();
+		let self_bindings: Vec<_> =
+			(0..fields.len()).map(|i| proc_macro2::Ident::new(&format!("self_{}", i), proc_macro2::Span::call_site())).collect();
+		let other_bindings: Vec<_> =
+			(0..fields.len()).map(|i| proc_macro2::Ident::new(&format!("other_{}", i), proc_macro2::Span::call_site())).collect();
+		let self_bindings = &self_bindings;
+		let other_bindings = &other_bindings;
+
+		let (self_pattern, other_pattern) = match self.variant_fields {
+			syn::Fields::Named(_) => {
+				let field_names: Vec<_> = fields.iter().map(|f| f.ident.as_ref().unwrap()).collect();
+				let field_names = &field_names;
+				(
+					quote!({ #(#field_names: ref #self_bindings),* }),
+					quote!({ #(#field_names: ref #other_bindings),* }),
+				)
 			},
 
-			(None, &LinkType::Custom) => {
-				let pattern = fields_pattern_ignore(&self.variant_fields);
+			syn::Fields::Unnamed(_) => (quote!((#(ref #self_bindings),*)), quote!((#(ref #other_bindings),*))),
 
-				quote! {
-					#error_kind_name::#variant_ident #pattern => stringify!(#variant_ident),
-				}
-			},
+			syn::Fields::Unit => (quote!(), quote!()),
+		};
+
+		// Spanned to the field so that a field type that doesn't implement `Ord` gets an error pointing at it
+		let cmps = fields.iter().zip(self_bindings.iter().zip(other_bindings.iter())).map(|(field, (self_binding, other_binding))| {
+			let span = syn::spanned::Spanned::span(&field.ty);
+			quote_spanned!(span=> .then_with(|| ::std::cmp::Ord::cmp(#self_binding, #other_binding)))
+		});
+
+		quote! {
+			#allow_deprecated
+			(&#error_kind_name::#variant_ident #self_pattern, &#error_kind_name::#variant_ident #other_pattern) =>
+				::std::cmp::Ordering::Equal #(#cmps)*,
 		}
 	}
 
 	fn error_kind_display_case(
 		&self,
 		error_kind_name: &proc_macro2::Ident,
+		fallback_display: Option<&syn::Expr>,
+		dynamic_description: bool,
+		debug_display: bool,
 	) -> proc_macro2::TokenStream {
+		let allow_deprecated = self.allow_deprecated();
+
 		let variant_ident = &self.variant_ident;
 
 		match (self.custom_display.as_ref(), &self.link_type) {
 			(_, &LinkType::Msg) => quote! {
+				#allow_deprecated
 				#error_kind_name::#variant_ident(ref s) => ::std::fmt::Display::fmt(s, f),
 			},
 
 			(Some(&CustomFormatter::FormatString { ref format_string, ref pattern, ref args }), &LinkType::Chainable(_, _)) => quote! {
+				#allow_deprecated
 				#error_kind_name::#variant_ident #pattern => write!(f, #format_string, #args),
 			},
 
-			(Some(&CustomFormatter::Expr(ref custom_display)), &LinkType::Chainable(_, _)) if is_closure(custom_display) => quote! {
-				#error_kind_name::#variant_ident(ref kind) => {
-					#[cfg_attr(feature = "cargo-clippy", allow(redundant_closure_call))]
-					{ (#custom_display)(kind) }
-				},
+			(Some(&CustomFormatter::Expr(ref custom_display)), &LinkType::Chainable(_, _)) if is_closure(custom_display) => {
+				let call = call_closure(custom_display, quote!(kind), 1);
+
+				quote! {
+					#allow_deprecated
+					#error_kind_name::#variant_ident(ref kind) => #call,
+				}
 			},
 
 			(Some(&CustomFormatter::Expr(ref custom_display)), &LinkType::Chainable(_, _)) => quote! {
+				#allow_deprecated
 				#error_kind_name::#variant_ident(ref kind) => #custom_display(f, kind),
 			},
 
 			(Some(&CustomFormatter::FormatString { ref format_string, ref pattern, ref args }), &LinkType::Foreign(_)) => quote! {
+				#allow_deprecated
 				#error_kind_name::#variant_ident #pattern => write!(f, #format_string, #args),
 			},
 
-			(Some(&CustomFormatter::Expr(ref custom_display)), &LinkType::Foreign(_)) if is_closure(custom_display) => quote! {
-				#error_kind_name::#variant_ident(ref err) => {
-					#[cfg_attr(feature = "cargo-clippy", allow(redundant_closure_call))]
-					{ (#custom_display)(err) }
-				},
+			(Some(&CustomFormatter::Expr(ref custom_display)), &LinkType::Foreign(_)) if is_closure(custom_display) => {
+				let pattern = single_field_pattern(&self.variant_fields, quote!(ref err));
+				let call = call_closure(custom_display, quote!(err), 1);
+
+				quote! {
+					#allow_deprecated
+					#error_kind_name::#variant_ident #pattern => #call,
+				}
 			},
 
-			(Some(&CustomFormatter::Expr(ref custom_display)), &LinkType::Foreign(_)) => quote! {
-				#error_kind_name::#variant_ident(ref err) => #custom_display(f, err),
+			(Some(&CustomFormatter::Expr(ref custom_display)), &LinkType::Foreign(_)) => {
+				let pattern = single_field_pattern(&self.variant_fields, quote!(ref err));
+
+				quote! {
+					#allow_deprecated
+					#error_kind_name::#variant_ident #pattern => #custom_display(f, err),
+				}
 			},
 
 			(Some(&CustomFormatter::FormatString { ref format_string, ref pattern, ref args }), &LinkType::Custom) => quote! {
+				#allow_deprecated
 				#error_kind_name::#variant_ident #pattern => write!(f, #format_string, #args),
 			},
 
@@ -1279,33 +3873,94 @@ impl Link {
 				let args = args(&self.variant_fields);
 
 				if is_closure(custom_display) {
+					let call = call_closure(custom_display, args, self.variant_fields.iter().count());
+
 					quote! {
-						#error_kind_name::#variant_ident #pattern => {
-							#[cfg_attr(feature = "cargo-clippy", allow(redundant_closure_call))]
-							{ (#custom_display)(#args) }
-						},
+						#allow_deprecated
+						#error_kind_name::#variant_ident #pattern => #call,
 					}
 				}
 				else {
 					quote! {
+						#allow_deprecated
 						#error_kind_name::#variant_ident #pattern => #custom_display(f, #args),
 					}
 				}
 			},
 
+			(None, _) if fallback_display.is_some() => {
+				let fallback_display = fallback_display.unwrap();
+				let pattern = fields_pattern_ignore(&self.variant_fields);
+
+				if is_closure(fallback_display) {
+					let call = call_closure(fallback_display, quote!(self), 1);
+
+					quote! {
+						#allow_deprecated
+						#error_kind_name::#variant_ident #pattern => #call,
+					}
+				}
+				else {
+					quote! {
+						#allow_deprecated
+						#error_kind_name::#variant_ident #pattern => #fallback_display(f, self),
+					}
+				}
+			},
+
 			(None, &LinkType::Chainable(_, _)) => quote! {
+				#allow_deprecated
 				#error_kind_name::#variant_ident(ref kind) => ::std::fmt::Display::fmt(kind, f),
 			},
 
-			(None, &LinkType::Foreign(_)) => quote! {
-				#error_kind_name::#variant_ident(ref err) => ::std::fmt::Display::fmt(err, f),
+			(None, &LinkType::Foreign(_)) => {
+				let pattern = single_field_pattern(&self.variant_fields, quote!(ref err));
+				let err = self.foreign_error_ref();
+
+				quote! {
+					#allow_deprecated
+					#error_kind_name::#variant_ident #pattern => ::std::fmt::Display::fmt(#err, f),
+				}
+			},
+
+			(None, &LinkType::Custom) if debug_display && self.custom_description.is_none() => {
+				let pattern = fields_pattern(&self.variant_fields);
+				let args = args(&self.variant_fields);
+
+				// Written like the derived `Debug` of the member, ie `Name(5, 6)` or `Name { line: 5, column: 6 }`
+				let name = unraw_name(variant_ident);
+				let format_string = match self.variant_fields {
+					syn::Fields::Named(syn::FieldsNamed { ref named, .. }) => {
+						let fields: Vec<_> = named.iter().map(|f| format!("{}: {{:?}}", unraw_name(f.ident.as_ref().unwrap()))).collect();
+						format!("{} {{{{ {} }}}}", name, fields.join(", "))
+					},
+
+					syn::Fields::Unnamed(syn::FieldsUnnamed { ref unnamed, .. }) =>
+						format!("{}({})", name, vec!["{:?}"; unnamed.len()].join(", ")),
+
+					syn::Fields::Unit => name,
+				};
+
+				quote! {
+					#allow_deprecated
+					#error_kind_name::#variant_ident #pattern => write!(f, #format_string, #args),
+				}
 			},
 
 			(None, &LinkType::Custom) => {
 				let pattern = fields_pattern_ignore(&self.variant_fields);
 
-				quote! {
-					#error_kind_name::#variant_ident #pattern => ::std::fmt::Display::fmt(self.description(), f),
+				if dynamic_description {
+					quote! {
+						#allow_deprecated
+						#error_kind_name::#variant_ident #pattern => ::std::fmt::Display::fmt(&*self.description(), f),
+					}
+				}
+				else {
+					quote! {
+						#allow_deprecated
+						#error_kind_name::#variant_ident #pattern => ::std::fmt::Display::fmt(self.description(), f),
+					}
 				}
 			},
 		}
@@ -1314,22 +3969,34 @@ impl Link {
 	fn error_kind_from_impl(
 		&self,
 		error_kind_name: &proc_macro2::Ident,
+		lifetime_a: &syn::Lifetime,
 		impl_generics: &syn::ImplGenerics, impl_generics_lifetime: &syn::ImplGenerics, ty_generics: &syn::TypeGenerics, where_clause: Option<&syn::WhereClause>,
 	) -> Option<proc_macro2::TokenStream> {
+		let allow_deprecated = self.allow_deprecated();
+
 		let variant_ident = &self.variant_ident;
 
 		match self.link_type {
 			LinkType::Msg => Some(quote! {
-				impl #impl_generics_lifetime From<&'__a str> for #error_kind_name #ty_generics #where_clause {
-					fn from(s: &'__a str) -> Self { #error_kind_name::#variant_ident(s.to_string()) }
+				#allow_deprecated
+				impl #impl_generics_lifetime From<&#lifetime_a str> for #error_kind_name #ty_generics #where_clause {
+					fn from(s: &#lifetime_a str) -> Self { #error_kind_name::#variant_ident(s.into()) }
 				}
 
+				#allow_deprecated
 				impl #impl_generics From<String> for #error_kind_name #ty_generics #where_clause {
-					fn from(s: String) -> Self { #error_kind_name::#variant_ident(s) }
+					#[cfg_attr(feature = "cargo-clippy", allow(useless_conversion))]
+					fn from(s: String) -> Self { #error_kind_name::#variant_ident(s.into()) }
+				}
+
+				#allow_deprecated
+				impl #impl_generics_lifetime From<::std::fmt::Arguments<#lifetime_a>> for #error_kind_name #ty_generics #where_clause {
+					fn from(args: ::std::fmt::Arguments<#lifetime_a>) -> Self { #error_kind_name::#variant_ident(args.to_string().into()) }
 				}
 			}),
 
 			LinkType::Chainable(_, ref error_kind_ty) => Some(quote! {
+				#allow_deprecated
 				impl #impl_generics From<#error_kind_ty> for #error_kind_name #ty_generics #where_clause {
 					fn from(kind: #error_kind_ty) -> Self {
 						#error_kind_name::#variant_ident(kind)
@@ -1346,6 +4013,8 @@ impl Link {
 		&self,
 		error_kind_name: &proc_macro2::Ident,
 	) -> Option<proc_macro2::TokenStream> {
+		let allow_deprecated = self.allow_deprecated();
+
 		let variant_ident = &self.variant_ident;
 
 		#[cfg_attr(feature = "cargo-clippy", allow(match_same_arms))]
@@ -1357,70 +4026,227 @@ impl Link {
 				let args = args(&self.variant_fields);
 
 				if is_closure(custom_cause) {
+					let call = call_closure(custom_cause, args, self.variant_fields.iter().count());
+
 					quote! {
+						#allow_deprecated
 						#error_kind_name::#variant_ident #pattern => {
-							#[cfg_attr(feature = "cargo-clippy", allow(redundant_closure_call))]
-							let result = (#custom_cause)(#args);
+							let result = #call;
 							Some(result)
 						},
 					}
 				}
 				else {
 					quote! {
+						#allow_deprecated
 						#error_kind_name::#variant_ident #pattern => Some(#custom_cause(#args)),
 					}
 				}
 			}),
 
-			(None, &LinkType::Foreign(_)) => Some(quote! {
-				#error_kind_name::#variant_ident(ref err) => ::std::error::Error::cause(err),
+			(None, &LinkType::Foreign(_)) => Some({
+				let pattern = single_field_pattern(&self.variant_fields, quote!(ref err));
+				let err = self.foreign_error_ref();
+
+				quote! {
+					#allow_deprecated
+					#error_kind_name::#variant_ident #pattern => ::std::error::Error::cause(#err),
+				}
 			}),
 
+			(None, &LinkType::Custom) if self.source_field.is_some() => self.error_source_case(error_kind_name),
+
 			(None, &LinkType::Chainable(_, _)) |
 			(None, &LinkType::Custom) => None,
 		}
 	}
 
+	fn error_source_case(&self, error_kind_name: &proc_macro2::Ident) -> Option<proc_macro2::TokenStream> {
+		let allow_deprecated = self.allow_deprecated();
+
+		let variant_ident = &self.variant_ident;
+
+		if let (None, &LinkType::Foreign(_)) = (self.custom_cause.as_ref(), &self.link_type) {
+			let pattern = single_field_pattern(&self.variant_fields, quote!(ref err));
+			let err = self.foreign_error_ref();
+
+			return Some(quote! {
+				#allow_deprecated
+				#error_kind_name::#variant_ident #pattern => ::std::error::Error::source(#err),
+			});
+		}
+
+		self.source_field.map(|_| {
+			let pattern = self.source_field_pattern();
+
+			quote! {
+				#allow_deprecated
+				#error_kind_name::#variant_ident #pattern => Some(source),
+			}
+		})
+	}
+
+	fn source_field_pattern(&self) -> proc_macro2::TokenStream {
+		let source_field = self.source_field.unwrap();
+
+		match self.variant_fields {
+			syn::Fields::Named(syn::FieldsNamed { ref named, .. }) => {
+				let field_name = named[source_field].ident.as_ref().unwrap();
+				quote!({ #field_name: ref source, .. })
+			},
+
+			syn::Fields::Unnamed(syn::FieldsUnnamed { ref unnamed, .. }) => {
+				let fields = (0..unnamed.len()).map(|i| if i == source_field { quote!(ref source) } else { quote!(_) });
+				quote!((#(#fields),*))
+			},
+
+			syn::Fields::Unit => unreachable!(),
+		}
+	}
+
 	fn error_from_impl(
 		&self,
 		error_kind_name: &proc_macro2::Ident, error_name: &proc_macro2::Ident,
 		generics: &std::collections::HashSet<&proc_macro2::Ident>,
+		track_caller: &Option<proc_macro2::TokenStream>, extra_args: &proc_macro2::TokenStream,
+		lifetime_a: &syn::Lifetime,
 		impl_generics: &syn::ImplGenerics, impl_generics_lifetime: &syn::ImplGenerics, ty_generics: &syn::TypeGenerics, where_clause: Option<&syn::WhereClause>,
 	) -> Option<proc_macro2::TokenStream> {
+		let allow_deprecated = self.allow_deprecated();
+
 		let variant_ident = &self.variant_ident;
 
 		match self.link_type {
 			LinkType::Msg => Some(quote! {
-				impl #impl_generics_lifetime From<&'__a str> for #error_name #ty_generics #where_clause {
-					fn from(s: &'__a str) -> Self { Self::from_kind(s.into()) }
+				impl #impl_generics_lifetime From<&#lifetime_a str> for #error_name #ty_generics #where_clause {
+					#track_caller
+					fn from(s: &#lifetime_a str) -> Self { Self::from_kind(s.into()) }
 				}
 
 				impl #impl_generics From<String> for #error_name #ty_generics #where_clause {
+					#track_caller
 					fn from(s: String) -> Self { Self::from_kind(s.into()) }
 				}
+
+				impl #impl_generics_lifetime From<::std::fmt::Arguments<#lifetime_a>> for #error_name #ty_generics #where_clause {
+					#track_caller
+					fn from(args: ::std::fmt::Arguments<#lifetime_a>) -> Self { Self::from_kind(args.into()) }
+				}
 			}),
 
 			LinkType::Chainable(ref error_ty, _) => Some(quote! {
+				#allow_deprecated
 				impl #impl_generics From<#error_ty> for #error_name #ty_generics #where_clause {
+					#track_caller
 					fn from(err: #error_ty) -> Self {
-						#error_name(#error_kind_name::#variant_ident(err.0), err.1)
+						#error_name(#error_kind_name::#variant_ident(err.0), err.1 #extra_args)
 					}
 				}
 			}),
 
 			// Don't emit From impl for any generics of the errorkind because they cause conflicting trait impl errors.
-			// ie don't emit `impl From<T> for Error<T>` even if there's a variant `SomeError(T)`
+			// ie don't emit `impl From<T> for Error<T>` even if there's a variant `SomeError(T)`, unless the user asked for it with `force_from`
 			LinkType::Foreign(syn::Type::Path(syn::TypePath { ref path, .. }))
-				if !path.global() && path.segments.len() == 1 && generics.contains(&path.segments[0].ident) => None,
+				if !self.force_from && !path.global() && path.segments.len() == 1 && generics.contains(&path.segments[0].ident) => None,
+
+			LinkType::Foreign(ref ty) => Some({
+				let fields = foreign_field_constructor(&self.variant_fields);
+
+				quote! {
+					#allow_deprecated
+					impl #impl_generics From<#ty> for #error_name #ty_generics #where_clause {
+						#track_caller
+						fn from(err: #ty) -> Self {
+							Self::from_kind(#error_kind_name::#variant_ident #fields)
+						}
+					}
+				}
+			}),
+
+			LinkType::Custom if self.from_field => Some({
+				let ty = &self.variant_fields.iter().nth(foreign_field_index(&self.variant_fields).unwrap()).unwrap().ty;
+				let fields = foreign_field_constructor(&self.variant_fields);
+
+				quote! {
+					#allow_deprecated
+					impl #impl_generics From<#ty> for #error_name #ty_generics #where_clause {
+						#track_caller
+						fn from(err: #ty) -> Self {
+							Self::from_kind(#error_kind_name::#variant_ident #fields)
+						}
+					}
+				}
+			}),
+
+			LinkType::Custom => None,
+		}
+	}
+
+	fn error_try_from_impl(
+		&self,
+		error_kind_name: &proc_macro2::Ident, error_name: &proc_macro2::Ident,
+		impl_generics: &syn::ImplGenerics, ty_generics: &syn::TypeGenerics, where_clause: Option<&syn::WhereClause>,
+	) -> proc_macro2::TokenStream {
+		let allow_deprecated = self.allow_deprecated();
+
+		let variant_ident = &self.variant_ident;
+
+		let field_tys: Vec<_> = self.variant_fields.iter().map(|field| &field.ty).collect();
+		let field_tys = &field_tys;
+		let field_names: Vec<_> = self.variant_fields.iter().enumerate().map(|(i, field)| match field.ident {
+			Some(ref ident) => ident.clone(),
+			None => proc_macro2::Ident::new(&format!("value{}", i), proc_macro2::Span::call_site()),
+		}).collect();
+		let field_names = &field_names;
+
+		let pattern = match self.variant_fields {
+			syn::Fields::Named(_) => quote!({ #(#field_names,)* }),
+			syn::Fields::Unnamed(_) => quote!((#(#field_names,)*)),
+			syn::Fields::Unit => quote!(),
+		};
+		let pattern_ignore = fields_pattern_ignore(&self.variant_fields);
+
+		// A single field is extracted as itself rather than as a one-element tuple
+		let (ty, value) = if field_tys.len() == 1 {
+			(quote!(#(#field_tys)*), quote!(#(#field_names)*))
+		}
+		else {
+			(quote!((#(#field_tys),*)), quote!((#(#field_names),*)))
+		};
+
+		quote! {
+			#allow_deprecated
+			impl #impl_generics ::std::convert::TryFrom<#error_name #ty_generics> for #ty #where_clause {
+				type Error = #error_name #ty_generics;
+
+				fn try_from(err: #error_name #ty_generics) -> ::std::result::Result<Self, Self::Error> {
+					if let #error_kind_name::#variant_ident #pattern_ignore = err.0 {
+						match err.0 {
+							#allow_deprecated
+							#error_kind_name::#variant_ident #pattern => Ok(#value),
+							_ => unreachable!(),
+						}
+					}
+					else {
+						Err(err)
+					}
+				}
+			}
+		}
+	}
 
-			LinkType::Foreign(ref ty) => Some(quote! {
-				impl #impl_generics From<#ty> for #error_name #ty_generics #where_clause {
-					fn from(err: #ty) -> Self {
-						Self::from_kind(#error_kind_name::#variant_ident(err))
+	fn chained_error_backtrace_case(&self) -> Option<proc_macro2::TokenStream> {
+		match self.link_type {
+			LinkType::Chainable(ref error_ty, _) => Some(quote! {
+				if let Some(err) = err.downcast_ref::<#error_ty>() {
+					if let Some(backtrace) = err.1.backtrace() {
+						return Some(backtrace);
 					}
 				}
 			}),
 
+			LinkType::Msg |
+			LinkType::Foreign(_) |
 			LinkType::Custom => None,
 		}
 	}
@@ -1447,7 +4273,7 @@ enum CustomFormatter {
 
 impl CustomFormatter {
 	fn parse(tokens: proc_macro2::TokenStream, attr_name: &str, variant_ident: &proc_macro2::Ident, variant_fields: &syn::Fields) -> Self {
-		let err = match syn::parse(tokens.clone().into()) {
+		let err = match syn::parse2(tokens.clone()) {
 			Ok(expr) => return CustomFormatter::Expr(expr),
 			Err(err) => err,
 		};
@@ -1496,6 +4322,38 @@ impl CustomFormatter {
 				attr_name, variant_ident, tt);
 		}
 
+		// The description is a `&'static str` that's returned as-is, so any parameters in it would not be substituted,
+		// and escaped braces like `{{` need to be unescaped here instead of by `format!`
+		if attr_name == "description" {
+			let description = format_string_without_parameters(&format_string, false).unwrap_or_else(|err| panic!(
+				"Could not parse `description` attribute of member {} - {}. Use the `display` attribute to include the fields of the member.",
+				variant_ident, err));
+
+			return CustomFormatter::FormatString {
+				format_string: description,
+				pattern: quote!(),
+				args: quote!(),
+			};
+		}
+
+		CustomFormatter::from_format_string(format_string, attr_name, variant_ident, variant_fields)
+	}
+
+	fn from_format_string(format_string: String, attr_name: &str, variant_ident: &proc_macro2::Ident, variant_fields: &syn::Fields) -> Self {
+		// `{description}` refers to the description of the member rather than to one of its fields
+		let description_arg = if has_description_parameter(&format_string) {
+			if variant_fields.iter().filter_map(|f| f.ident.as_ref()).any(|field_name| field_name == "description") {
+				panic!(
+					"Could not parse `{}` attribute of member {} - `{{description}}` is ambiguous since the member has a field named `description`",
+					attr_name, variant_ident);
+			}
+
+			quote!(description = self.description(),)
+		}
+		else {
+			quote!()
+		};
+
 		match *variant_fields {
 			syn::Fields::Named(syn::FieldsNamed { ref named, .. }) => {
 				let referenced_names = get_parameter_names(&format_string).unwrap_or_else(|err| panic!(
@@ -1516,7 +4374,7 @@ impl CustomFormatter {
 				CustomFormatter::FormatString {
 					format_string,
 					pattern: quote!({ #(#patterns,)* }),
-					args: quote!(#(#args)*),
+					args: quote!(#(#args)* #description_arg),
 				}
 			},
 
@@ -1538,19 +4396,19 @@ impl CustomFormatter {
 				CustomFormatter::FormatString {
 					format_string,
 					pattern: quote!((#(#patterns,)*)),
-					args: quote!(#(#args)*),
+					args: quote!(#(#args)* #description_arg),
 				}
 			},
 
 			syn::Fields::Unit => {
-				ensure_no_parameters(&format_string).unwrap_or_else(|err| panic!(
+				format_string_without_parameters(&format_string, true).unwrap_or_else(|err| panic!(
 					"Could not parse `{}` attribute of member {} - {}",
 					attr_name, variant_ident, err));
 
 				CustomFormatter::FormatString {
 					format_string,
 					pattern: quote!(),
-					args: quote!(),
+					args: description_arg,
 				}
 			},
 		}
@@ -1563,6 +4421,94 @@ fn is_error_chain_attribute(attr: &syn::Attribute) -> bool {
 		return segment.ident == "error_chain" && segment.arguments.is_empty();
 	}
 
+	// Derive helper attributes can't be qualified, so this would otherwise be silently ignored here and fail later as an unknown attribute
+	match attr.path.segments.iter().last() {
+		Some(segment) if segment.ident == "error_chain" =>
+			panic!("Could not parse attribute `{}` - `error_chain` attributes must be written as `#[error_chain(...)]` without a path", quote!(#attr)),
+
+		_ => false,
+	}
+}
+
+fn child_module_visibility(vis: &syn::Visibility) -> proc_macro2::TokenStream {
+	// Items re-exported from a child module need the visibility of the original item, but relative to the parent module
+	match *vis {
+		syn::Visibility::Restricted(syn::VisRestricted { ref path, .. }) => {
+			let first_segment = path.segments.first().map(|segment| segment.value().ident.to_string()).unwrap_or_default();
+			match &*first_segment {
+				"self" => {
+					let rest = path.segments.iter().skip(1);
+					quote!(pub(in super #(::#rest)*))
+				},
+
+				"super" => quote!(pub(in super::#path)),
+
+				_ => quote!(#vis),
+			}
+		},
+
+		syn::Visibility::Inherited => quote!(pub(super)),
+
+		syn::Visibility::Public(_) |
+		syn::Visibility::Crate(_) => quote!(#vis),
+	}
+}
+
+fn unique_ident(name: &str, generics: &syn::Generics) -> proc_macro2::Ident {
+	let mut name = name.to_string();
+
+	while generics.params.iter().any(|param| match *param {
+		syn::GenericParam::Type(syn::TypeParam { ref ident, .. }) |
+		syn::GenericParam::Const(syn::ConstParam { ref ident, .. }) => ident == &name,
+		syn::GenericParam::Lifetime(syn::LifetimeDef { ref lifetime, .. }) => lifetime.ident == name,
+	}) {
+		name.push('_');
+	}
+
+	proc_macro2::Ident::new(&name, proc_macro2::Span::call_site())
+}
+
+// `stringify!` keeps the `r#` prefix of raw identifiers, which is not wanted in names shown to users
+fn unraw_name(ident: &proc_macro2::Ident) -> String {
+	ident.to_string().trim_start_matches("r#").to_string()
+}
+
+// Converts a `CamelCase` variant name to `snake_case`, keeping acronyms together, ie `HTTPStatus` becomes `http_status`
+fn to_snake_case(name: &str) -> String {
+	let chars: Vec<_> = name.chars().collect();
+	let mut result = String::new();
+
+	for (i, &c) in chars.iter().enumerate() {
+		if c.is_uppercase() {
+			if i > 0 && chars[i - 1] != '_' {
+				let previous_is_lower = chars[i - 1].is_lowercase() || chars[i - 1].is_numeric();
+				let next_is_lower = i + 1 < chars.len() && chars[i + 1].is_lowercase();
+				if previous_is_lower || (chars[i - 1].is_uppercase() && next_is_lower) {
+					result.push('_');
+				}
+			}
+
+			result.extend(c.to_lowercase());
+		}
+		else {
+			result.push(c);
+		}
+	}
+
+	result
+}
+
+fn is_box_str(segment: &syn::PathSegment) -> bool {
+	if segment.ident != "Box" {
+		return false;
+	}
+
+	if let syn::PathArguments::AngleBracketed(syn::AngleBracketedGenericArguments { ref args, .. }) = segment.arguments {
+		if let Some(&syn::GenericArgument::Type(syn::Type::Path(syn::TypePath { ref path, .. }))) = args.iter().next() {
+			return args.len() == 1 && !path.global() && path.segments.len() == 1 && path.segments[0].ident == "str";
+		}
+	}
+
 	false
 }
 
@@ -1575,6 +4521,28 @@ fn is_closure(expr: &syn::Expr) -> bool {
 	}
 }
 
+/// Calls a closure with the given arguments through a generic function, instead of calling it directly like `(closure)(args)`.
+///
+/// Passing the closure to a function with an `FnOnce` bound makes the compiler infer the types of its parameters from the arguments
+/// before it checks the closure's body, so the body can do things like dereference them or call methods on them.
+fn call_closure(closure: &syn::Expr, args: proc_macro2::TokenStream, num_args: usize) -> proc_macro2::TokenStream {
+	let params: Vec<_> = (0..num_args).map(|i| proc_macro2::Ident::new(&format!("A{}", i), proc_macro2::Span::call_site())).collect();
+	let values: Vec<_> = (0..num_args).map(|i| proc_macro2::Ident::new(&format!("a{}", i), proc_macro2::Span::call_site())).collect();
+	let params = &params;
+	let values = &values;
+
+	// The helper is in scope for the closure's body too, so it's named to not shadow any user item the closure refers to
+	quote! {
+		{
+			fn __derive_error_chain_call<#(#params,)* R, F: FnOnce(#(#params),*) -> R>(func: F, #(#values: #params),*) -> R {
+				func(#(#values),*)
+			}
+
+			__derive_error_chain_call(#closure, #args)
+		}
+	}
+}
+
 fn fields_pattern(variant_fields: &syn::Fields) -> proc_macro2::TokenStream {
 	match *variant_fields {
 		syn::Fields::Named(syn::FieldsNamed { ref named, .. }) => {
@@ -1597,6 +4565,96 @@ fn fields_pattern(variant_fields: &syn::Fields) -> proc_macro2::TokenStream {
 	}
 }
 
+fn link_type_from_word(word: &str, variant_ident: &proc_macro2::Ident, variant_fields: &syn::Fields) -> LinkType {
+	match word {
+		"foreign" => match foreign_field_index(variant_fields) {
+			Some(index) => LinkType::Foreign(variant_fields.iter().nth(index).unwrap().ty.clone()),
+
+			None => panic!(
+				"Foreign link {} must have a single field (the foreign error type), or mark all its other fields with `#[error_chain(default)]`.",
+				variant_ident),
+		},
+
+		"custom" => LinkType::Custom,
+
+		_ => panic!(
+			"Could not parse `error_chain` attribute of member {} - expected one of `foreign`, `custom`, `force_from`, `extract` but got {}",
+			variant_ident, word),
+	}
+}
+
+fn add_link_type(link_types: &mut Vec<(LinkType, String)>, new_link_type: LinkType, declaration: String) {
+	// Repeating the same declaration is harmless. Different ones are collected so that the conflict error can name all of them.
+	if link_types.iter().all(|link_type| link_type.1 != declaration) {
+		link_types.push((new_link_type, declaration));
+	}
+}
+
+fn single_field_pattern(variant_fields: &syn::Fields, binding: proc_macro2::TokenStream) -> proc_macro2::TokenStream {
+	let index = foreign_field_index(variant_fields).unwrap();
+
+	match *variant_fields {
+		syn::Fields::Named(syn::FieldsNamed { ref named, .. }) => {
+			let field_name = named[index].ident.as_ref().unwrap();
+			quote!({ #field_name: #binding, .. })
+		},
+
+		syn::Fields::Unnamed(syn::FieldsUnnamed { ref unnamed, .. }) => {
+			let fields = (0..unnamed.len()).map(|i| if i == index { binding.clone() } else { quote!(_) });
+			quote!((#(#fields),*))
+		},
+
+		syn::Fields::Unit => unreachable!(),
+	}
+}
+
+// The fields of a foreign link when it's constructed from the foreign error, with any other fields set to their defaults
+fn foreign_field_constructor(variant_fields: &syn::Fields) -> proc_macro2::TokenStream {
+	let index = foreign_field_index(variant_fields).unwrap();
+
+	let values = (0..variant_fields.iter().count()).map(|i| if i == index { quote!(err) } else { quote!(::std::default::Default::default()) });
+
+	match *variant_fields {
+		syn::Fields::Named(syn::FieldsNamed { ref named, .. }) => {
+			let field_names = named.into_iter().map(|f| f.ident.as_ref().unwrap());
+			quote!({ #(#field_names: #values,)* })
+		},
+
+		syn::Fields::Unnamed(_) => quote!((#(#values),*)),
+
+		syn::Fields::Unit => unreachable!(),
+	}
+}
+
+// Other fields of a foreign link can be marked with `#[error_chain(default)]`, so the foreign error is the only field that isn't marked
+fn foreign_field_index(variant_fields: &syn::Fields) -> Option<usize> {
+	let mut unmarked_fields = variant_fields.iter().enumerate()
+		.filter(|&(_, field)| !field.attrs.iter().any(is_default_field_attribute))
+		.map(|(i, _)| i);
+
+	match (unmarked_fields.next(), unmarked_fields.next()) {
+		(Some(index), None) => Some(index),
+		_ => None,
+	}
+}
+
+// The `#[from]` field attribute of thiserror, which is accepted to ease migrating from it
+fn is_from_field_attribute(attr: &syn::Attribute) -> bool {
+	!attr.path.global() && attr.path.segments.len() == 1 && attr.path.segments[0].ident == "from"
+}
+
+fn is_default_field_attribute(attr: &syn::Attribute) -> bool {
+	if is_error_chain_attribute(attr) {
+		if let Some(syn::Meta::List(syn::MetaList { ref nested, .. })) = attr.interpret_meta() {
+			if let Some(&syn::NestedMeta::Meta(syn::Meta::Word(ref ident))) = nested.iter().next() {
+				return nested.len() == 1 && ident == "default";
+			}
+		}
+	}
+
+	false
+}
+
 fn fields_pattern_ignore(variant_fields: &syn::Fields) -> proc_macro2::TokenStream {
 	match *variant_fields {
 		syn::Fields::Named(syn::FieldsNamed { .. }) => quote!({ .. }),
@@ -1653,26 +4711,90 @@ fn get_parameter_positions(format_string: &str) -> Result<std::collections::Hash
 		syntex_fmt_macros::Piece::NextArgument(syntex_fmt_macros::Argument { position, .. }) => match position {
 			syntex_fmt_macros::Position::ArgumentNext => Some(Err("expected positional parameter but found `{}`".to_string())),
 			syntex_fmt_macros::Position::ArgumentIs(index) => Some(Ok(index)),
+			syntex_fmt_macros::Position::ArgumentNamed("description") => None,
 			syntex_fmt_macros::Position::ArgumentNamed(name) => Some(Err(format!("expected positional parameter but found `{{{}}}`", name))),
 		},
 	})
 	.collect()
 }
 
-fn ensure_no_parameters(format_string: &str) -> Result<(), String> {
+fn has_description_parameter(format_string: &str) -> bool {
+	let parser = syntex_fmt_macros::Parser::new(format_string);
+
+	for piece in parser {
+		if let syntex_fmt_macros::Piece::NextArgument(syntex_fmt_macros::Argument { position: syntex_fmt_macros::Position::ArgumentNamed(name), .. }) = piece {
+			if name == "description" {
+				return true;
+			}
+		}
+	}
+
+	false
+}
+
+// `allow_description` allows the `{description}` parameter, which is dropped from the result
+fn format_string_without_parameters(format_string: &str, allow_description: bool) -> Result<String, String> {
 	let parser = syntex_fmt_macros::Parser::new(format_string);
+	let mut result = String::new();
 
 	for piece in parser {
 		match piece {
-			syntex_fmt_macros::Piece::String(_) => (),
+			syntex_fmt_macros::Piece::String(s) => result.push_str(s),
 
 			syntex_fmt_macros::Piece::NextArgument(syntex_fmt_macros::Argument { position, .. }) => match position {
 				syntex_fmt_macros::Position::ArgumentNext => return Err("expected no parameters but found `{}`".to_string()),
 				syntex_fmt_macros::Position::ArgumentIs(index) => return Err(format!("expected no parameters but found `{{{}}}`", index)),
+				syntex_fmt_macros::Position::ArgumentNamed("description") if allow_description => (),
 				syntex_fmt_macros::Position::ArgumentNamed(name) => return Err(format!("expected no parameters but found `{{{}}}`", name)),
 			},
 		}
 	}
 
-	Ok(())
+	Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::Link;
+
+	#[test]
+	#[should_panic(expected = "Member Io has conflicting link types `custom` and `foreign`.")]
+	fn conflicting_link_types() {
+		let variant: ::syn::Variant = parse_quote! {
+			#[error_chain(custom)]
+			#[error_chain(foreign)]
+			Io(::std::io::Error)
+		};
+		let _ = Link::from(variant);
+	}
+
+	#[test]
+	#[should_panic(expected = "Member Io has conflicting link types `custom` and `foreign`.")]
+	fn conflicting_link_types_reversed() {
+		let variant: ::syn::Variant = parse_quote! {
+			#[error_chain(foreign, custom)]
+			Io(::std::io::Error)
+		};
+		let _ = Link::from(variant);
+	}
+
+	#[test]
+	#[should_panic(expected = "`error_chain` attributes must be written as `#[error_chain(...)]` without a path")]
+	fn qualified_attribute() {
+		let variant: ::syn::Variant = parse_quote! {
+			#[derive_error_chain::error_chain(custom)]
+			Code(i32)
+		};
+		let _ = Link::from(variant);
+	}
+
+	#[test]
+	#[should_panic(expected = "`{description}` is ambiguous since the member has a field named `description`")]
+	fn ambiguous_description_parameter() {
+		let variant: ::syn::Variant = parse_quote! {
+			#[error_chain(custom, display = const("{description}: {code}"))]
+			Code { description: String, code: i32 }
+		};
+		let _ = Link::from(variant);
+	}
 }